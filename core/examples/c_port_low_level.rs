@@ -7,7 +7,7 @@ use std::{
 
 use tigerbeetle_unofficial_core as tb;
 
-const MAX_MESSAGE_SIZE: usize = (1024 * 1024) - 256;
+const MAX_MESSAGE_SIZE: usize = tb::MESSAGE_BODY_SIZE_MAX;
 
 struct Callbacks;
 
@@ -67,7 +67,7 @@ fn main() {
 
     println!("Creating transfers...");
     const MAX_BATCHES: usize = 100;
-    const TRANSFERS_PER_BATCH: usize = MAX_MESSAGE_SIZE / mem::size_of::<tb::Transfer>();
+    const TRANSFERS_PER_BATCH: usize = tb::MAX_BATCH_TRANSFERS;
     let max_batches = std::env::var("TIGERBEETLE_RS_MAX_BATCHES")
         .ok()
         .and_then(|s| s.parse().ok())
@@ -0,0 +1,51 @@
+use std::sync::mpsc;
+
+use tigerbeetle_unofficial_core as tb;
+
+struct UserData {
+    accounts: [tb::Account; 2],
+}
+
+impl tb::UserData for UserData {
+    fn data(&self) -> &[u8] {
+        bytemuck::cast_slice(&self.accounts)
+    }
+}
+
+fn main() {
+    let address = std::env::var("TB_ADDRESS");
+    let address = address.as_deref().unwrap_or("3000");
+
+    // Replies are sent back to the main thread through a channel. The closure
+    // is called from the tigerbeetle thread, so everything it captures must
+    // be `Sync`.
+    let (reply_sender, reply_receiver) = mpsc::channel();
+    let client = tb::Client::with_closure(
+        0,
+        address.as_bytes(),
+        1,
+        move |packet: tb::Packet<'_, Box<UserData>>, payload: &[u8]| {
+            let status = packet.status();
+            drop(packet.into_user_data());
+            let results = bytemuck::pod_collect_to_vec(payload);
+            reply_sender
+                .send(status.map(|()| tb::error::CreateAccountsApiError::from_raw_results(results)))
+                .unwrap();
+        },
+    )
+    .expect("Failed to initialize tigerbeetle client");
+
+    let user_data = Box::new(UserData {
+        accounts: [tb::Account::new(1, 777, 2), tb::Account::new(2, 777, 2)],
+    });
+    client
+        .acquire(user_data, tb::OperationKind::CreateAccounts.into())
+        .unwrap()
+        .submit();
+
+    match reply_receiver.recv().unwrap() {
+        Ok(None) => println!("Accounts created successfully"),
+        Ok(Some(e)) => println!("Failed to create accounts: {e}"),
+        Err(e) => println!("Failed to send request: {e}"),
+    }
+}
@@ -2,6 +2,8 @@ use std::time::{Duration, SystemTime};
 
 use bytemuck::{Pod, TransparentWrapper, Zeroable};
 
+use crate::error::FromBytesError;
+
 mod balance;
 mod filter;
 
@@ -14,6 +16,32 @@ pub use sys::tb_account_t as Raw;
 #[derive(Clone, Copy, TransparentWrapper, Pod, Zeroable)]
 pub struct Account(Raw);
 
+/// Account id, typed to not confuse debit and credit accounts of a transfer.
+///
+/// # Examples
+///
+/// ```
+/// use tigerbeetle_unofficial_core::{Account, Transfer};
+///
+/// let debit = Account::new(1, 1, 1);
+/// let credit = Account::new(2, 1, 1);
+/// let transfer = Transfer::new(3)
+///     .with_debit(debit.account_id())
+///     .with_credit(credit.account_id());
+/// assert_eq!(transfer.debit_account_id(), 1);
+/// assert_eq!(transfer.credit_account_id(), 2);
+/// ```
+///
+/// Plain ids aren't accepted where an account id is expected:
+///
+/// ```compile_fail
+/// use tigerbeetle_unofficial_core::Transfer;
+///
+/// let transfer = Transfer::new(3).with_debit(1u128);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AccountId(pub u128);
+
 impl Account {
     #[track_caller]
     pub fn new(id: u128, ledger: u32, code: u16) -> Self {
@@ -26,6 +54,9 @@ impl Account {
     pub const fn from_raw(raw: Raw) -> Self {
         Account(raw)
     }
+    pub fn from_raw_ref(raw: &Raw) -> &Self {
+        Self::wrap_ref(raw)
+    }
     pub const fn into_raw(self) -> Raw {
         self.0
     }
@@ -55,6 +86,29 @@ impl Account {
         self
     }
 
+    /// Same as [`Self::id`], but as [`uuid::Uuid`].
+    ///
+    /// Uuid bytes are the big-endian representation of the `u128` value, as
+    /// in [`uuid::Uuid::from_u128`].
+    #[cfg(feature = "uuid")]
+    pub fn id_uuid(&self) -> uuid::Uuid {
+        uuid::Uuid::from_u128(self.id())
+    }
+    #[cfg(feature = "uuid")]
+    #[track_caller]
+    pub fn set_id_uuid(&mut self, id: uuid::Uuid) {
+        self.set_id(id.as_u128());
+    }
+    #[cfg(feature = "uuid")]
+    #[track_caller]
+    pub fn with_id_uuid(mut self, id: uuid::Uuid) -> Self {
+        self.set_id_uuid(id);
+        self
+    }
+    pub const fn account_id(&self) -> AccountId {
+        AccountId(self.0.id)
+    }
+
     pub const fn user_data_128(&self) -> u128 {
         self.0.user_data_128
     }
@@ -66,6 +120,26 @@ impl Account {
         self
     }
 
+    /// Same as [`Self::user_data_128`], but as [`uuid::Uuid`].
+    ///
+    /// Uuid bytes are the big-endian representation of the `u128` value, as
+    /// in [`uuid::Uuid::from_u128`].
+    #[cfg(feature = "uuid")]
+    pub fn user_data_128_uuid(&self) -> uuid::Uuid {
+        uuid::Uuid::from_u128(self.user_data_128())
+    }
+    #[cfg(feature = "uuid")]
+    #[track_caller]
+    pub fn set_user_data_128_uuid(&mut self, user_data_128: uuid::Uuid) {
+        self.set_user_data_128(user_data_128.as_u128());
+    }
+    #[cfg(feature = "uuid")]
+    #[track_caller]
+    pub fn with_user_data_128_uuid(mut self, user_data_128: uuid::Uuid) -> Self {
+        self.set_user_data_128_uuid(user_data_128);
+        self
+    }
+
     pub const fn user_data_64(&self) -> u64 {
         self.0.user_data_64
     }
@@ -140,9 +214,61 @@ impl Account {
         self.0.credits_posted
     }
 
+    /// Amount that can still be debited or credited without violating the
+    /// balance constraint of this account, saturating at `i128` bounds.
+    ///
+    /// - With [`Flags::CREDITS_MUST_NOT_EXCEED_DEBITS`] it's
+    ///   `debits_posted - credits_posted - credits_pending`.
+    /// - Otherwise, including [`Flags::DEBITS_MUST_NOT_EXCEED_CREDITS`], it's
+    ///   `credits_posted - debits_posted - debits_pending`.
+    ///
+    /// Negative value means pending transfers may exceed the balance.
+    pub fn available_balance(&self) -> i128 {
+        if self.flags().contains(Flags::CREDITS_MUST_NOT_EXCEED_DEBITS) {
+            signed_sub(
+                self.0.debits_posted,
+                self.0.credits_posted.saturating_add(self.0.credits_pending),
+            )
+        } else {
+            signed_sub(
+                self.0.credits_posted,
+                self.0.debits_posted.saturating_add(self.0.debits_pending),
+            )
+        }
+    }
+
+    /// All balances as `(debits_pending, debits_posted, credits_pending,
+    /// credits_posted)`.
+    pub const fn balances(&self) -> (u128, u128, u128, u128) {
+        (
+            self.0.debits_pending,
+            self.0.debits_posted,
+            self.0.credits_pending,
+            self.0.credits_posted,
+        )
+    }
+
     pub fn timestamp(&self) -> SystemTime {
         SystemTime::UNIX_EPOCH + Duration::from_nanos(self.0.timestamp)
     }
+    /// Nanoseconds since unix epoch.
+    pub const fn timestamp_nanos(&self) -> u64 {
+        self.0.timestamp
+    }
+    /// Same as [`Self::timestamp`], but as [`chrono::DateTime`].
+    #[cfg(feature = "chrono")]
+    pub fn timestamp_chrono(&self) -> chrono::DateTime<chrono::Utc> {
+        crate::util::chrono_time::from_nanos(self.0.timestamp)
+    }
+}
+
+/// `a - b` saturating at `i128` bounds.
+fn signed_sub(a: u128, b: u128) -> i128 {
+    if a >= b {
+        i128::try_from(a - b).unwrap_or(i128::MAX)
+    } else {
+        i128::try_from(b - a).map_or(i128::MIN, |d| -d)
+    }
 }
 
 impl std::fmt::Debug for Account {
@@ -164,6 +290,39 @@ impl std::fmt::Debug for Account {
     }
 }
 
+/// Zeroed account.
+///
+/// Zero id is invalid, so set it with [`Account::set_id`] or [`Account::with_id`]
+/// before submission.
+impl Default for Account {
+    fn default() -> Self {
+        Account(Raw::zeroed())
+    }
+}
+
+/// Conversion of domain types into accounts, see
+/// `Client::create_accounts_from` of the high level client.
+pub trait IntoAccount {
+    fn into_account(self) -> Account;
+}
+
+impl IntoAccount for Account {
+    fn into_account(self) -> Account {
+        self
+    }
+}
+
+impl From<u128> for AccountId {
+    fn from(value: u128) -> Self {
+        AccountId(value)
+    }
+}
+impl From<AccountId> for u128 {
+    fn from(value: AccountId) -> Self {
+        value.0
+    }
+}
+
 impl From<Raw> for Account {
     fn from(value: Raw) -> Self {
         Account(value)
@@ -174,3 +333,126 @@ impl From<Account> for Raw {
         value.0
     }
 }
+
+/// Decodes the raw in-memory layout, e.g. `bytemuck::bytes_of(&x)`. Slice
+/// doesn't need to be aligned.
+impl TryFrom<&[u8]> for Account {
+    type Error = FromBytesError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        bytemuck::try_pod_read_unaligned(value).map_err(|_| FromBytesError {
+            len: value.len(),
+            expected: std::mem::size_of::<Self>(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_raw_ref() {
+        let raw = Raw::zeroed();
+        assert!(std::ptr::eq(Account::from_raw_ref(&raw).as_raw(), &raw));
+    }
+
+    #[test]
+    fn default_is_zeroed() {
+        assert_eq!(
+            bytemuck::bytes_of(&Account::default()),
+            bytemuck::bytes_of(&Account::zeroed()),
+        );
+    }
+
+    fn account(flags: Flags, balances: (u128, u128, u128, u128)) -> Account {
+        let mut account = Account::new(1, 1, 1).with_flags(flags);
+        (
+            account.0.debits_pending,
+            account.0.debits_posted,
+            account.0.credits_pending,
+            account.0.credits_posted,
+        ) = balances;
+        account
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn uuid_round_trip() {
+        let id = uuid::Uuid::from_u128(0x0123_4567_89ab_cdef_0123_4567_89ab_cdef);
+        let user_data = uuid::Uuid::from_bytes([7; 16]);
+        let account = Account::new(1, 1, 1)
+            .with_id_uuid(id)
+            .with_user_data_128_uuid(user_data);
+        assert_eq!(account.id_uuid(), id);
+        assert_eq!(account.id(), id.as_u128());
+        assert_eq!(account.user_data_128_uuid(), user_data);
+        assert_eq!(account.user_data_128(), user_data.as_u128());
+    }
+
+    #[test]
+    fn account_id_round_trip() {
+        let id = AccountId::from(42);
+        assert_eq!(u128::from(id), 42);
+        assert_eq!(Account::new(42, 1, 1).account_id(), id);
+
+        let transfer = crate::Transfer::new(1)
+            .with_debit(AccountId(u128::MAX - 1))
+            .with_credit(id);
+        assert_eq!(transfer.debit_account_id(), u128::MAX - 1);
+        assert_eq!(transfer.credit_account_id(), 42);
+    }
+
+    #[test]
+    fn balances_order() {
+        assert_eq!(
+            account(Flags::empty(), (1, 2, 3, 4)).balances(),
+            (1, 2, 3, 4)
+        );
+        let a = account(Flags::empty(), (1, 2, 3, 4));
+        assert_eq!(
+            a.balances(),
+            (
+                a.debits_pending(),
+                a.debits_posted(),
+                a.credits_pending(),
+                a.credits_posted()
+            )
+        );
+    }
+
+    #[test]
+    fn available_balance_credit_account() {
+        let a = account(Flags::DEBITS_MUST_NOT_EXCEED_CREDITS, (10, 20, 1000, 100));
+        assert_eq!(a.available_balance(), 70);
+        let a = account(Flags::empty(), (10, 100, 0, 50));
+        assert_eq!(a.available_balance(), -60);
+    }
+
+    #[test]
+    fn available_balance_debit_account() {
+        let a = account(Flags::CREDITS_MUST_NOT_EXCEED_DEBITS, (1000, 100, 10, 20));
+        assert_eq!(a.available_balance(), 70);
+        let a = account(Flags::CREDITS_MUST_NOT_EXCEED_DEBITS, (0, 50, 10, 100));
+        assert_eq!(a.available_balance(), -60);
+    }
+
+    #[test]
+    fn available_balance_saturates() {
+        let a = account(Flags::empty(), (0, 0, 0, u128::MAX));
+        assert_eq!(a.available_balance(), i128::MAX);
+        let a = account(Flags::empty(), (u128::MAX, 1, 0, 0));
+        assert_eq!(a.available_balance(), i128::MIN);
+    }
+
+    #[test]
+    fn signed_sub_bounds() {
+        assert_eq!(signed_sub(5, 3), 2);
+        assert_eq!(signed_sub(3, 5), -2);
+        assert_eq!(signed_sub(i128::MAX as u128, 0), i128::MAX);
+        assert_eq!(signed_sub(i128::MAX as u128 + 1, 0), i128::MAX);
+        assert_eq!(signed_sub(0, i128::MAX as u128), -i128::MAX);
+        assert_eq!(signed_sub(0, i128::MAX as u128 + 1), i128::MIN);
+        assert_eq!(signed_sub(0, u128::MAX), i128::MIN);
+    }
+}
@@ -12,6 +12,9 @@ impl Balance {
     pub const fn from_raw(raw: Raw) -> Self {
         Balance(raw)
     }
+    pub fn from_raw_ref(raw: &Raw) -> &Self {
+        Self::wrap_ref(raw)
+    }
     pub const fn into_raw(self) -> Raw {
         self.0
     }
@@ -66,9 +69,30 @@ impl Balance {
         self
     }
 
+    /// All balances as `(debits_pending, debits_posted, credits_pending,
+    /// credits_posted)`.
+    pub const fn balances(&self) -> (u128, u128, u128, u128) {
+        (
+            self.0.debits_pending,
+            self.0.debits_posted,
+            self.0.credits_pending,
+            self.0.credits_posted,
+        )
+    }
+
+    /// Net posted balance, `credits_posted - debits_posted`, saturating at
+    /// `i128` bounds.
+    pub fn net_posted(&self) -> i128 {
+        super::signed_sub(self.0.credits_posted, self.0.debits_posted)
+    }
+
     pub fn timestamp(&self) -> SystemTime {
         SystemTime::UNIX_EPOCH + Duration::from_nanos(self.0.timestamp)
     }
+    /// Nanoseconds since unix epoch.
+    pub const fn timestamp_nanos(&self) -> u64 {
+        self.0.timestamp
+    }
 }
 
 impl std::fmt::Debug for Balance {
@@ -77,12 +101,18 @@ impl std::fmt::Debug for Balance {
             .field("debits_pending", &self.0.debits_pending)
             .field("debits_posted", &self.0.debits_posted)
             .field("credits_pending", &self.0.credits_pending)
-            .field("credits_pending", &self.0.credits_pending)
+            .field("credits_posted", &self.0.credits_posted)
             .field("timestamp", &self.0.timestamp)
             .finish_non_exhaustive()
     }
 }
 
+impl Default for Balance {
+    fn default() -> Self {
+        Balance(Raw::zeroed())
+    }
+}
+
 impl From<Raw> for Balance {
     fn from(value: Raw) -> Self {
         Balance(value)
@@ -93,3 +123,46 @@ impl From<Balance> for Raw {
         value.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_raw_ref() {
+        let raw = Raw::zeroed();
+        assert!(std::ptr::eq(Balance::from_raw_ref(&raw).as_raw(), &raw));
+    }
+
+    #[test]
+    fn default_is_zeroed() {
+        assert_eq!(
+            bytemuck::bytes_of(&Balance::default()),
+            bytemuck::bytes_of(&Balance::zeroed()),
+        );
+    }
+
+    #[test]
+    fn debug_prints_every_balance_field() {
+        let balance = Balance::default()
+            .with_debits_pending(1)
+            .with_debits_posted(2)
+            .with_credits_pending(3)
+            .with_credits_posted(4);
+        let s = format!("{balance:?}");
+        assert!(s.contains("debits_pending: 1,"), "{s}");
+        assert!(s.contains("debits_posted: 2,"), "{s}");
+        assert!(s.contains("credits_pending: 3,"), "{s}");
+        assert!(s.contains("credits_posted: 4,"), "{s}");
+    }
+
+    #[test]
+    fn balances_order() {
+        let balance = Balance::default()
+            .with_debits_pending(1)
+            .with_debits_posted(2)
+            .with_credits_pending(3)
+            .with_credits_posted(4);
+        assert_eq!(balance.balances(), (1, 2, 3, 4));
+    }
+}
@@ -2,6 +2,8 @@ use std::time::{Duration, SystemTime};
 
 use bytemuck::{Pod, TransparentWrapper, Zeroable};
 
+use crate::error::FilterLimitError;
+
 pub use sys::generated_safe::AccountFilterFlags as Flags;
 pub use sys::tb_account_filter_t as Raw;
 
@@ -22,6 +24,20 @@ impl std::fmt::Debug for Filter {
 }
 
 impl Filter {
+    /// Highest useful limit, as a single reply holds at most this many
+    /// transfers or balances.
+    pub const MAX_LIMIT: u32 = {
+        let transfers = crate::max_batch_len::<crate::Transfer>();
+        let balances = crate::max_batch_len::<super::Balance>();
+        let max = if transfers < balances {
+            transfers
+        } else {
+            balances
+        };
+        assert!(max <= u32::MAX as usize);
+        max as u32
+    };
+
     #[track_caller]
     pub fn new(account_id: u128, limit: u32) -> Self {
         Filter(Raw::zeroed())
@@ -32,6 +48,9 @@ impl Filter {
     pub const fn from_raw(raw: Raw) -> Self {
         Filter(raw)
     }
+    pub fn from_raw_ref(raw: &Raw) -> &Self {
+        Self::wrap_ref(raw)
+    }
     pub const fn into_raw(self) -> Raw {
         self.0
     }
@@ -54,6 +73,7 @@ impl Filter {
         self
     }
 
+    /// Inclusive lower bound of returned timestamps. Zero means no bound.
     pub fn timestamp_min(&self) -> SystemTime {
         SystemTime::UNIX_EPOCH + Duration::from_nanos(self.0.timestamp_min)
     }
@@ -63,14 +83,54 @@ impl Filter {
             .ok()
             .and_then(|t| t.as_nanos().try_into().ok())
             .expect("failed to get nanoseconds since unix epoch from the argument");
-        assert_ne!(t, u64::MAX, "timestamp_min must not be `2^64 - 1`");
-        self.0.timestamp_min = t;
+        self.set_timestamp_min_nanos(t);
     }
     pub fn with_timestamp_min(mut self, timestamp_min: SystemTime) -> Self {
         self.set_timestamp_min(timestamp_min);
         self
     }
 
+    /// Nanoseconds since unix epoch.
+    pub const fn timestamp_min_nanos(&self) -> u64 {
+        self.0.timestamp_min
+    }
+    pub fn set_timestamp_min_nanos(&mut self, timestamp_min: u64) {
+        assert_ne!(
+            timestamp_min,
+            u64::MAX,
+            "timestamp_min must not be `2^64 - 1`"
+        );
+        self.0.timestamp_min = timestamp_min;
+    }
+    pub fn with_timestamp_min_nanos(mut self, timestamp_min: u64) -> Self {
+        self.set_timestamp_min_nanos(timestamp_min);
+        self
+    }
+
+    #[cfg(feature = "chrono")]
+    pub fn timestamp_min_chrono(&self) -> chrono::DateTime<chrono::Utc> {
+        crate::util::chrono_time::from_nanos(self.0.timestamp_min)
+    }
+    /// # Panics
+    ///
+    /// Panics if `timestamp_min` is before unix epoch. Leap second is clamped
+    /// to the last nanosecond of the preceding second.
+    #[cfg(feature = "chrono")]
+    pub fn set_timestamp_min_chrono(&mut self, timestamp_min: chrono::DateTime<chrono::Utc>) {
+        let t = crate::util::chrono_time::to_nanos(timestamp_min)
+            .expect("failed to get nanoseconds since unix epoch from the argument");
+        self.set_timestamp_min_nanos(t);
+    }
+    #[cfg(feature = "chrono")]
+    pub fn with_timestamp_min_chrono(
+        mut self,
+        timestamp_min: chrono::DateTime<chrono::Utc>,
+    ) -> Self {
+        self.set_timestamp_min_chrono(timestamp_min);
+        self
+    }
+
+    /// Inclusive upper bound of returned timestamps. Zero means no bound.
     pub fn timestamp_max(&self) -> SystemTime {
         SystemTime::UNIX_EPOCH + Duration::from_nanos(self.0.timestamp_max)
     }
@@ -80,17 +140,60 @@ impl Filter {
             .ok()
             .and_then(|t| t.as_nanos().try_into().ok())
             .expect("failed to get nanoseconds since unix epoch from the argument");
-        assert_ne!(t, u64::MAX, "timestamp_max must not be `2^64 - 1`");
-        self.0.timestamp_max = t;
+        self.set_timestamp_max_nanos(t);
     }
     pub fn with_timestamp_max(mut self, timestamp_max: SystemTime) -> Self {
         self.set_timestamp_max(timestamp_max);
         self
     }
 
+    /// Nanoseconds since unix epoch.
+    pub const fn timestamp_max_nanos(&self) -> u64 {
+        self.0.timestamp_max
+    }
+    pub fn set_timestamp_max_nanos(&mut self, timestamp_max: u64) {
+        assert_ne!(
+            timestamp_max,
+            u64::MAX,
+            "timestamp_max must not be `2^64 - 1`"
+        );
+        self.0.timestamp_max = timestamp_max;
+    }
+    pub fn with_timestamp_max_nanos(mut self, timestamp_max: u64) -> Self {
+        self.set_timestamp_max_nanos(timestamp_max);
+        self
+    }
+
+    #[cfg(feature = "chrono")]
+    pub fn timestamp_max_chrono(&self) -> chrono::DateTime<chrono::Utc> {
+        crate::util::chrono_time::from_nanos(self.0.timestamp_max)
+    }
+    /// # Panics
+    ///
+    /// Panics if `timestamp_max` is before unix epoch. Leap second is clamped
+    /// to the last nanosecond of the preceding second.
+    #[cfg(feature = "chrono")]
+    pub fn set_timestamp_max_chrono(&mut self, timestamp_max: chrono::DateTime<chrono::Utc>) {
+        let t = crate::util::chrono_time::to_nanos(timestamp_max)
+            .expect("failed to get nanoseconds since unix epoch from the argument");
+        self.set_timestamp_max_nanos(t);
+    }
+    #[cfg(feature = "chrono")]
+    pub fn with_timestamp_max_chrono(
+        mut self,
+        timestamp_max: chrono::DateTime<chrono::Utc>,
+    ) -> Self {
+        self.set_timestamp_max_chrono(timestamp_max);
+        self
+    }
+
     pub const fn limit(&self) -> u32 {
         self.0.limit
     }
+    /// Limits above [`Self::MAX_LIMIT`] are accepted, but the reply is
+    /// silently truncated to [`Self::MAX_LIMIT`] items with no indication
+    /// that more exist. Use [`Self::try_with_limit`] or
+    /// [`Self::with_limit_capped`] to handle them explicitly.
     pub fn set_limit(&mut self, limit: u32) {
         assert_ne!(limit, 0, "limit must not be zero");
         self.0.limit = limit;
@@ -99,6 +202,51 @@ impl Filter {
         self.set_limit(limit);
         self
     }
+    /// Set limit clamped to [`Self::MAX_LIMIT`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tigerbeetle_unofficial_core::account::Filter;
+    ///
+    /// let filter = Filter::new(1, 1).with_limit_capped(u32::MAX);
+    /// assert_eq!(filter.limit(), Filter::MAX_LIMIT);
+    ///
+    /// let filter = Filter::new(1, 1).with_limit_capped(10);
+    /// assert_eq!(filter.limit(), 10);
+    /// ```
+    pub fn with_limit_capped(self, limit: u32) -> Self {
+        self.with_limit(limit.min(Self::MAX_LIMIT))
+    }
+    /// Set limit.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FilterLimitError`] if `limit` is zero or above
+    /// [`Self::MAX_LIMIT`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tigerbeetle_unofficial_core::account::Filter;
+    ///
+    /// let filter = Filter::new(1, 1).try_with_limit(Filter::MAX_LIMIT).unwrap();
+    /// assert_eq!(filter.limit(), Filter::MAX_LIMIT);
+    ///
+    /// let e = Filter::new(1, 1).try_with_limit(0).unwrap_err();
+    /// assert_eq!(e.limit(), 0);
+    ///
+    /// let e = Filter::new(1, 1)
+    ///     .try_with_limit(Filter::MAX_LIMIT + 1)
+    ///     .unwrap_err();
+    /// assert_eq!(e.limit(), Filter::MAX_LIMIT + 1);
+    /// ```
+    pub fn try_with_limit(self, limit: u32) -> Result<Self, FilterLimitError> {
+        if limit == 0 || limit > Self::MAX_LIMIT {
+            return Err(FilterLimitError { limit });
+        }
+        Ok(self.with_limit(limit))
+    }
 
     pub const fn flags(&self) -> Flags {
         Flags::from_bits_retain(self.0.flags)
@@ -110,6 +258,28 @@ impl Filter {
         self.0.flags = flags.bits();
         self
     }
+
+    /// Include transfers debiting the account.
+    pub const fn with_debits(self) -> Self {
+        self.with_flags(self.flags().union(Flags::DEBITS))
+    }
+    /// Include transfers crediting the account.
+    pub const fn with_credits(self) -> Self {
+        self.with_flags(self.flags().union(Flags::CREDITS))
+    }
+    /// Return results in reverse chronological order, newest first.
+    pub const fn with_reversed(self) -> Self {
+        self.with_flags(self.flags().union(Flags::REVERSED))
+    }
+    pub const fn is_reversed(&self) -> bool {
+        self.flags().contains(Flags::REVERSED)
+    }
+}
+
+impl Default for Filter {
+    fn default() -> Self {
+        Filter(Raw::zeroed())
+    }
 }
 
 impl From<Raw> for Filter {
@@ -122,3 +292,22 @@ impl From<Filter> for Raw {
         value.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_raw_ref() {
+        let raw = Raw::zeroed();
+        assert!(std::ptr::eq(Filter::from_raw_ref(&raw).as_raw(), &raw));
+    }
+
+    #[test]
+    fn default_is_zeroed() {
+        assert_eq!(
+            bytemuck::bytes_of(&Filter::default()),
+            bytemuck::bytes_of(&Filter::zeroed()),
+        );
+    }
+}
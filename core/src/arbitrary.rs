@@ -0,0 +1,131 @@
+//! [`Arbitrary`] implementations generating entities which pass client side
+//! validation, so fuzzers exercise ingestion code instead of hitting panics
+//! in setters.
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{
+    account::{self, Filter, FilterFlags},
+    transfer, Account, Transfer,
+};
+
+/// Id other than `0` and `u128::MAX`.
+fn id(u: &mut Unstructured<'_>) -> Result<u128> {
+    u.int_in_range(1..=u128::MAX - 1)
+}
+
+/// Amount accepted by [`Transfer::with_amount`]: at most `i128::MAX`, or
+/// occasionally `u128::MAX` meaning the whole pending or balancing amount.
+fn amount(u: &mut Unstructured<'_>) -> Result<u128> {
+    if u.ratio(1, 16)? {
+        Ok(u128::MAX)
+    } else {
+        u.int_in_range(0..=i128::MAX as u128)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Account {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut flags = account::Flags::from_bits_truncate(u.arbitrary()?);
+        if flags.contains(
+            account::Flags::DEBITS_MUST_NOT_EXCEED_CREDITS
+                | account::Flags::CREDITS_MUST_NOT_EXCEED_DEBITS,
+        ) {
+            flags.remove(account::Flags::CREDITS_MUST_NOT_EXCEED_DEBITS);
+        }
+        Ok(Account::new(
+            id(u)?,
+            u.int_in_range(1..=u32::MAX)?,
+            u.int_in_range(1..=u16::MAX)?,
+        )
+        .with_user_data_128(u.arbitrary()?)
+        .with_user_data_64(u.arbitrary()?)
+        .with_user_data_32(u.arbitrary()?)
+        .with_flags(flags))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Transfer {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        use transfer::Flags;
+
+        let id = id(u)?;
+        let mut transfer = Transfer::new(id)
+            .with_user_data_128(u.arbitrary()?)
+            .with_user_data_64(u.arbitrary()?)
+            .with_user_data_32(u.arbitrary()?)
+            .with_ledger(u.int_in_range(1..=u32::MAX)?)
+            .with_code(u.int_in_range(1..=u16::MAX)?)
+            .with_amount(amount(u)?);
+        let mut flags = Flags::empty();
+        flags.set(Flags::LINKED, u.arbitrary()?);
+
+        let phase = *u.choose(&[
+            Flags::empty(),
+            Flags::PENDING,
+            Flags::POST_PENDING_TRANSFER,
+            Flags::VOID_PENDING_TRANSFER,
+        ])?;
+        if phase.intersects(Flags::POST_PENDING_TRANSFER | Flags::VOID_PENDING_TRANSFER) {
+            let mut pending_id = self::id(u)?;
+            if pending_id == id {
+                pending_id = if id == 1 { 2 } else { id - 1 };
+            }
+            transfer.set_pending_id(pending_id);
+            transfer.set_flags(flags | phase);
+            return Ok(transfer);
+        }
+
+        let debit = self::id(u)?;
+        let mut credit = self::id(u)?;
+        if credit == debit {
+            credit = if debit == 1 { 2 } else { debit - 1 };
+        }
+        flags.set(Flags::BALANCING_DEBIT, u.arbitrary()?);
+        flags.set(Flags::BALANCING_CREDIT, u.arbitrary()?);
+        if !flags.intersects(Flags::BALANCING_DEBIT | Flags::BALANCING_CREDIT)
+            && transfer.amount() == 0
+        {
+            transfer.set_amount(1);
+        }
+        if phase.contains(Flags::PENDING) {
+            transfer.set_timeout(u.arbitrary()?);
+        }
+        Ok(transfer
+            .with_debit_account_id(debit)
+            .with_credit_account_id(credit)
+            .with_flags(flags | phase))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Filter {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut flags = *u.choose(&[
+            FilterFlags::DEBITS,
+            FilterFlags::CREDITS,
+            FilterFlags::DEBITS | FilterFlags::CREDITS,
+        ])?;
+        flags.set(FilterFlags::REVERSED, u.arbitrary()?);
+        let timestamp_min = u.int_in_range(0..=u64::MAX - 1)?;
+        let timestamp_max = u.int_in_range(timestamp_min..=u64::MAX - 1)?;
+        Ok(Filter::new(id(u)?, u.int_in_range(1..=Filter::MAX_LIMIT)?)
+            .with_timestamp_min_nanos(timestamp_min)
+            .with_timestamp_max_nanos(timestamp_max)
+            .with_flags(flags))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transfer_amount_in_range() {
+        let data: Vec<u8> = (0..=u8::MAX).cycle().take(1 << 16).collect();
+        let mut u = Unstructured::new(&data);
+        while !u.is_empty() {
+            let amount = Transfer::arbitrary(&mut u).unwrap().amount();
+            assert!(amount <= i128::MAX as u128 || amount == u128::MAX);
+        }
+    }
+}
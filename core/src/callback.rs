@@ -1,6 +1,6 @@
 use std::{marker::PhantomData, panic::catch_unwind, slice};
 
-use crate::util::RawConstPtr;
+use crate::{error::SendErrorKind, util::RawConstPtr};
 
 use super::Packet;
 
@@ -86,6 +86,12 @@ where
     CallbacksFn::new(f)
 }
 
+/// Context passed to `tb_client_init` and back to [`on_completion_raw_fn`].
+pub(crate) struct CompletionContext<F> {
+    pub(crate) cluster_id: u128,
+    pub(crate) on_completion: *const F,
+}
+
 pub(crate) unsafe extern "C" fn on_completion_raw_fn<F>(
     ctx: usize,
     raw_client: sys::tb_client_t,
@@ -96,26 +102,41 @@ pub(crate) unsafe extern "C" fn on_completion_raw_fn<F>(
     F: Callbacks,
 {
     let _ = catch_unwind(|| {
-        let cb = &*sptr::from_exposed_addr::<F>(ctx);
-        let payload_size = payload_size
-                    .try_into()
-                    .expect("At the start of calling on_completion callback: unable to convert payload_size from u32 into usize");
-        let payload = if payload_size != 0 {
-            slice::from_raw_parts(payload, payload_size)
-        } else {
-            &[]
-        };
-        let packet = Packet {
+        let ctx = &*sptr::from_exposed_addr::<CompletionContext<F>>(ctx);
+        let cb = &*ctx.on_completion;
+        let mut packet = Packet {
             raw: packet,
             handle: super::ClientHandle {
                 raw: raw_client,
+                cluster_id: ctx.cluster_id,
                 on_completion: cb,
             },
         };
+        // Infallible on 32 and 64-bit platforms. Otherwise deliver the reply
+        // as an error instead of unwinding and losing the packet.
+        let Ok(payload_size) = usize::try_from(payload_size) else {
+            packet.set_status(Err(SendErrorKind::TooMuchData.into()));
+            return cb.on_completion(packet, &[]);
+        };
+        let payload = if payload_size != 0 {
+            slice::from_raw_parts(payload, payload_size)
+        } else {
+            &[]
+        };
         cb.on_completion(packet, payload)
     });
 }
 
+/// Owning pointer to packet's user data.
+///
+/// Pointee must be `Sized`, because `tb_packet_t` stores user data as a thin
+/// pointer, so slice pointers like `Box<[T]>` can't be user data. Wrap them
+/// into a sized type implementing [`UserData`] instead. To pass
+/// `Box<[Transfer]>` as a payload, convert it into
+/// [`SendOwnedSlice`](crate::util::SendOwnedSlice), which already accepts
+/// any [`RawConstPtr`] to a slice.
+///
+/// [`Transfer`]: crate::Transfer
 // `Self: Send` because we are sending user_data into the callback as an
 // argument.
 pub trait UserDataPtr: RawConstPtr<Target = Self::Pointee> + Send + user_data_ptr::Sealed {
@@ -147,3 +168,14 @@ pub trait UserData {
     /// Borrow the data to send.
     fn data(&self) -> &[u8];
 }
+
+/// Allows type erased user data. User data pointer must be thin, so use
+/// `Box<Box<dyn UserData + Send>>` rather than `Box<dyn UserData + Send>`.
+impl<T> UserData for Box<T>
+where
+    T: UserData + ?Sized,
+{
+    fn data(&self) -> &[u8] {
+        (**self).data()
+    }
+}
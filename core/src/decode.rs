@@ -0,0 +1,142 @@
+//! Decoders of reply payloads, e.g. for tools replaying replies captured
+//! off the wire.
+//!
+//! Every reply is a packed array of fixed size structures from `tb_client.h`
+//! in little endian byte order, without any header or padding between items.
+//! Empty payload is a valid reply of zero items.
+
+use std::mem;
+
+use crate::{
+    account::Balance,
+    error::{CreateAccountsApiError, CreateTransfersApiError, DecodeError},
+    Account, Transfer,
+};
+
+/// Decode `create_accounts` reply of 8 byte `tb_create_accounts_result_t`
+/// items, each being `u32` index of an account in the request followed by
+/// `u32` result code. Only failed accounts are present.
+///
+/// Returns `Ok(None)` if every account was created.
+///
+/// # Errors
+///
+/// Returns [`DecodeError`] if `payload` isn't a whole number of items.
+pub fn create_accounts(payload: &[u8]) -> Result<Option<CreateAccountsApiError>, DecodeError> {
+    decode(payload).map(CreateAccountsApiError::from_raw_results)
+}
+
+/// Decode `create_transfers` reply of 8 byte `tb_create_transfers_result_t`
+/// items, each being `u32` index of a transfer in the request followed by
+/// `u32` result code. Only failed transfers are present.
+///
+/// Returns `Ok(None)` if every transfer was created.
+///
+/// # Errors
+///
+/// Returns [`DecodeError`] if `payload` isn't a whole number of items.
+pub fn create_transfers(payload: &[u8]) -> Result<Option<CreateTransfersApiError>, DecodeError> {
+    decode(payload).map(CreateTransfersApiError::from_raw_results)
+}
+
+/// Decode `get_account_balances` reply of 128 byte `tb_account_balance_t`
+/// items.
+///
+/// # Errors
+///
+/// Returns [`DecodeError`] if `payload` isn't a whole number of items.
+pub fn get_account_balances(payload: &[u8]) -> Result<Vec<Balance>, DecodeError> {
+    decode(payload)
+}
+
+/// Decode `get_account_transfers` reply of 128 byte `tb_transfer_t` items.
+///
+/// # Errors
+///
+/// Returns [`DecodeError`] if `payload` isn't a whole number of items.
+pub fn get_account_transfers(payload: &[u8]) -> Result<Vec<Transfer>, DecodeError> {
+    decode(payload)
+}
+
+/// Decode `lookup_accounts` reply of 128 byte `tb_account_t` items. Only
+/// found accounts are present.
+///
+/// # Errors
+///
+/// Returns [`DecodeError`] if `payload` isn't a whole number of items.
+pub fn lookup_accounts(payload: &[u8]) -> Result<Vec<Account>, DecodeError> {
+    decode(payload)
+}
+
+/// Decode `lookup_transfers` reply of 128 byte `tb_transfer_t` items. Only
+/// found transfers are present.
+///
+/// # Errors
+///
+/// Returns [`DecodeError`] if `payload` isn't a whole number of items.
+pub fn lookup_transfers(payload: &[u8]) -> Result<Vec<Transfer>, DecodeError> {
+    decode(payload)
+}
+
+/// Same as [`lookup_transfers`], but reads transfers one by one as the
+/// iterator advances, without collecting them into a vector.
+///
+/// # Errors
+///
+/// Returns [`DecodeError`] if `payload` isn't a whole number of items.
+pub fn lookup_transfers_iter(
+    payload: &[u8],
+) -> Result<impl ExactSizeIterator<Item = Transfer> + '_, DecodeError> {
+    check_len::<Transfer>(payload)?;
+    Ok(payload
+        .chunks_exact(mem::size_of::<Transfer>())
+        .map(bytemuck::pod_read_unaligned))
+}
+
+/// Copy payload into a vector of items.
+///
+/// Empty payload, e.g. from looking up only missing ids, yields an empty
+/// vector without touching the allocator.
+pub(crate) fn decode<T: bytemuck::Pod>(payload: &[u8]) -> Result<Vec<T>, DecodeError> {
+    check_len::<T>(payload)?;
+    if payload.is_empty() {
+        return Ok(Vec::new());
+    }
+    Ok(bytemuck::pod_collect_to_vec(payload))
+}
+
+fn check_len<T>(payload: &[u8]) -> Result<(), DecodeError> {
+    let item_size = mem::size_of::<T>();
+    if payload.len() % item_size != 0 {
+        return Err(DecodeError {
+            len: payload.len(),
+            item_size,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup_transfers_iter_unaligned() {
+        let bytes = crate::transfer::serialize_batch(&[Transfer::new(1), Transfer::new(2)]);
+        let mut shifted = vec![0; bytes.len() + 1];
+        shifted[1..].copy_from_slice(&bytes);
+        let ids: Vec<_> = lookup_transfers_iter(&shifted[1..])
+            .unwrap()
+            .map(|t| t.id())
+            .collect();
+        assert_eq!(ids, [1, 2]);
+        assert_eq!(lookup_transfers_iter(&[]).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn lookup_transfers_iter_truncated() {
+        let bytes = crate::transfer::serialize_batch(&[Transfer::new(1)]);
+        let e = lookup_transfers_iter(&bytes[1..]).err().unwrap();
+        assert_eq!(e.blob_len(), bytes.len() - 1);
+    }
+}
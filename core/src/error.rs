@@ -40,6 +40,20 @@ pub struct CreateAccountsApiError(Vec<CreateAccountsIndividualApiError>);
 pub enum CreateAccountsError {
     Send(SendError),
     Api(CreateAccountsApiError),
+    /// Tigerbeetle rejected the whole batch as [`SendErrorKind::TooMuchData`].
+    ///
+    /// Produced by the high level client in place of
+    /// `Send(TooMuchData)`, so this kind never shows up under
+    /// [`Self::Send`] there. Split accounts into batches of at most
+    /// [`BatchTooLarge::max_items`] and retry.
+    BatchTooLarge(BatchTooLarge),
+    /// Reply couldn't be decoded, so it's unknown which accounts were created.
+    MalformedReply(MalformedReply),
+    /// Request was aborted by the high level client before its reply
+    /// arrived, so it's unknown which accounts were created.
+    Aborted,
+    /// No packet could be acquired, so nothing was sent.
+    Acquire(AcquirePacketError),
 }
 
 #[derive(Clone, Copy)]
@@ -64,6 +78,29 @@ pub struct CreateTransfersApiError(Vec<CreateTransfersIndividualApiError>);
 pub enum CreateTransfersError {
     Send(SendError),
     Api(CreateTransfersApiError),
+    /// Tigerbeetle rejected the whole batch as [`SendErrorKind::TooMuchData`].
+    ///
+    /// Produced by the high level client in place of
+    /// `Send(TooMuchData)`, so this kind never shows up under
+    /// [`Self::Send`] there. Split transfers into batches of at most
+    /// [`BatchTooLarge::max_items`] and retry.
+    BatchTooLarge(BatchTooLarge),
+    /// Reply couldn't be decoded, so it's unknown which transfers were created.
+    MalformedReply(MalformedReply),
+    /// Request was aborted by the high level client before its reply
+    /// arrived, so it's unknown which transfers were created.
+    Aborted,
+    /// No packet could be acquired, so nothing was sent.
+    Acquire(AcquirePacketError),
+}
+
+/// Batch didn't fit into a single request, reported by tigerbeetle as
+/// [`SendErrorKind::TooMuchData`].
+#[derive(Clone, Copy, Debug)]
+pub struct BatchTooLarge {
+    pub(crate) items: usize,
+    pub(crate) max_items: usize,
+    pub(crate) source: SendError,
 }
 
 impl NewClientError {
@@ -100,7 +137,7 @@ impl std::fmt::Debug for NewClientError {
 
 impl std::fmt::Display for NewClientError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self.kind())
+        f.write_str(self.kind().description())
     }
 }
 
@@ -134,6 +171,14 @@ impl AcquirePacketError {
     pub fn code(self) -> NonZeroU32 {
         self.0
     }
+
+    /// Whether acquiring a packet again could succeed.
+    ///
+    /// Only [`AcquirePacketErrorKind::ConcurrencyMaxExceeded`] is transient,
+    /// as packets are returned to the pool when requests complete.
+    pub fn is_retriable(self) -> bool {
+        matches!(self.kind(), AcquirePacketErrorKind::ConcurrencyMaxExceeded)
+    }
 }
 
 impl std::fmt::Debug for AcquirePacketError {
@@ -151,7 +196,7 @@ impl std::fmt::Debug for AcquirePacketError {
 
 impl std::fmt::Display for AcquirePacketError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self.kind())
+        f.write_str(self.kind().description())
     }
 }
 
@@ -185,6 +230,13 @@ impl SendError {
     pub fn code(self) -> NonZeroU8 {
         self.0
     }
+
+    /// Whether resending the same request could succeed.
+    ///
+    /// See [`SendErrorKind::is_retriable`].
+    pub fn is_retriable(self) -> bool {
+        self.kind().is_retriable()
+    }
 }
 
 impl std::fmt::Debug for SendError {
@@ -202,7 +254,7 @@ impl std::fmt::Debug for SendError {
 
 impl std::fmt::Display for SendError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self.kind())
+        f.write_str(self.kind().description())
     }
 }
 
@@ -253,7 +305,7 @@ impl std::fmt::Debug for CreateAccountError {
 
 impl std::fmt::Display for CreateAccountError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self.kind())
+        f.write_str(self.kind().description())
     }
 }
 
@@ -365,6 +417,11 @@ impl CreateAccountsApiError {
         &self.0
     }
 
+    /// Get a vec of individual errors. Never empty.
+    pub fn into_vec(self) -> Vec<CreateAccountsIndividualApiError> {
+        self.0
+    }
+
     /// Create error from vec of raw results.
     ///
     /// # Errors
@@ -380,6 +437,23 @@ impl CreateAccountsApiError {
     pub fn from_raw_results(v: Vec<RawCreateAccountsIndividualApiResult>) -> Option<Self> {
         Self::from_errors(CreateAccountsIndividualApiError::vec_from_raw_results(v))
     }
+
+    /// Get the first error which is not
+    /// [`CreateAccountErrorKind::LinkedEventFailed`].
+    ///
+    /// Failure of one account in a linked chain fails every other account of that
+    /// chain with `LinkedEventFailed`, so this is the actual cause.
+    pub fn chain_root(&self) -> Option<&CreateAccountsIndividualApiError> {
+        self.root_causes().next()
+    }
+
+    /// Iterate over errors skipping cascading
+    /// [`CreateAccountErrorKind::LinkedEventFailed`] ones.
+    pub fn root_causes(&self) -> impl Iterator<Item = &CreateAccountsIndividualApiError> {
+        self.0
+            .iter()
+            .filter(|e| !matches!(e.kind(), CreateAccountErrorKind::LinkedEventFailed))
+    }
 }
 
 impl AsRef<[CreateAccountsIndividualApiError]> for CreateAccountsApiError {
@@ -410,11 +484,33 @@ impl From<CreateAccountsIndividualApiError> for CreateAccountsApiError {
     }
 }
 
+impl CreateAccountsError {
+    /// Flatten errors into `(index, kind)` pairs, e.g. for structured error
+    /// reporting.
+    ///
+    /// Api errors become one pair per failed account. Any other error, like a
+    /// transport [`SendError`], failed the whole batch and has no index or
+    /// [`CreateAccountErrorKind`] of its own, so it becomes a single
+    /// `(0, CreateAccountErrorKind::UnstableUncategorized)` pair, which
+    /// describes itself as "unknown error". Match on `self` instead if you
+    /// need to tell those apart.
+    pub fn into_failures(self) -> Vec<(u32, CreateAccountErrorKind)> {
+        match self {
+            CreateAccountsError::Api(e) => e.0.iter().map(|e| (e.index(), e.kind())).collect(),
+            _ => vec![(0, CreateAccountErrorKind::UnstableUncategorized)],
+        }
+    }
+}
+
 impl std::error::Error for CreateAccountsError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         Some(match self {
             CreateAccountsError::Send(e) => e as _,
             CreateAccountsError::Api(e) => e as _,
+            CreateAccountsError::BatchTooLarge(e) => e as _,
+            CreateAccountsError::MalformedReply(e) => e as _,
+            CreateAccountsError::Aborted => return None,
+            CreateAccountsError::Acquire(e) => e as _,
         })
     }
 }
@@ -426,6 +522,10 @@ impl std::fmt::Display for CreateAccountsError {
                 "error occured while sending packets for accounts' creation"
             }
             CreateAccountsError::Api(_) => "api errors occured at accounts' creation",
+            CreateAccountsError::BatchTooLarge(e) => return e.fmt(f),
+            CreateAccountsError::MalformedReply(e) => return e.fmt(f),
+            CreateAccountsError::Aborted => "request was aborted before its reply arrived",
+            CreateAccountsError::Acquire(e) => return e.fmt(f),
         }
         .fmt(f)
     }
@@ -443,6 +543,24 @@ impl From<CreateAccountsApiError> for CreateAccountsError {
     }
 }
 
+impl From<BatchTooLarge> for CreateAccountsError {
+    fn from(value: BatchTooLarge) -> Self {
+        CreateAccountsError::BatchTooLarge(value)
+    }
+}
+
+impl From<AcquirePacketError> for CreateAccountsError {
+    fn from(value: AcquirePacketError) -> Self {
+        CreateAccountsError::Acquire(value)
+    }
+}
+
+impl From<MalformedReply> for CreateAccountsError {
+    fn from(value: MalformedReply) -> Self {
+        CreateAccountsError::MalformedReply(value)
+    }
+}
+
 impl CreateTransferError {
     const CODE_RANGE: std::ops::RangeInclusive<u32> =
         sys_safe::MIN_CREATE_TRANSFER_ERROR_CODE..=sys_safe::MAX_CREATE_TRANSFER_ERROR_CODE;
@@ -477,7 +595,7 @@ impl std::fmt::Debug for CreateTransferError {
 
 impl std::fmt::Display for CreateTransferError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self.kind())
+        f.write_str(self.kind().description())
     }
 }
 
@@ -604,6 +722,23 @@ impl CreateTransfersApiError {
     pub fn from_raw_results(v: Vec<RawCreateTransfersIndividualApiResult>) -> Option<Self> {
         Self::from_errors(CreateTransfersIndividualApiError::vec_from_raw_results(v))
     }
+
+    /// Get the first error which is not
+    /// [`CreateTransferErrorKind::LinkedEventFailed`].
+    ///
+    /// Failure of one transfer in a linked chain fails every other transfer of that
+    /// chain with `LinkedEventFailed`, so this is the actual cause.
+    pub fn chain_root(&self) -> Option<&CreateTransfersIndividualApiError> {
+        self.root_causes().next()
+    }
+
+    /// Iterate over errors skipping cascading
+    /// [`CreateTransferErrorKind::LinkedEventFailed`] ones.
+    pub fn root_causes(&self) -> impl Iterator<Item = &CreateTransfersIndividualApiError> {
+        self.0
+            .iter()
+            .filter(|e| !matches!(e.kind(), CreateTransferErrorKind::LinkedEventFailed))
+    }
 }
 
 impl AsRef<[CreateTransfersIndividualApiError]> for CreateTransfersApiError {
@@ -639,6 +774,10 @@ impl std::error::Error for CreateTransfersError {
         Some(match self {
             CreateTransfersError::Send(e) => e as _,
             CreateTransfersError::Api(e) => e as _,
+            CreateTransfersError::BatchTooLarge(e) => e as _,
+            CreateTransfersError::MalformedReply(e) => e as _,
+            CreateTransfersError::Aborted => return None,
+            CreateTransfersError::Acquire(e) => e as _,
         })
     }
 }
@@ -650,6 +789,10 @@ impl std::fmt::Display for CreateTransfersError {
                 "error occured while sending packets for transfers' creation"
             }
             CreateTransfersError::Api(_) => "api errors occured at transfers' creation",
+            CreateTransfersError::BatchTooLarge(e) => return e.fmt(f),
+            CreateTransfersError::MalformedReply(e) => return e.fmt(f),
+            CreateTransfersError::Aborted => "request was aborted before its reply arrived",
+            CreateTransfersError::Acquire(e) => return e.fmt(f),
         }
         .fmt(f)
     }
@@ -666,3 +809,353 @@ impl From<CreateTransfersApiError> for CreateTransfersError {
         CreateTransfersError::Api(value)
     }
 }
+
+impl From<BatchTooLarge> for CreateTransfersError {
+    fn from(value: BatchTooLarge) -> Self {
+        CreateTransfersError::BatchTooLarge(value)
+    }
+}
+
+impl From<AcquirePacketError> for CreateTransfersError {
+    fn from(value: AcquirePacketError) -> Self {
+        CreateTransfersError::Acquire(value)
+    }
+}
+
+impl From<MalformedReply> for CreateTransfersError {
+    fn from(value: MalformedReply) -> Self {
+        CreateTransfersError::MalformedReply(value)
+    }
+}
+
+impl BatchTooLarge {
+    /// Recognize `source` as rejection of a batch of `items` values of `T`,
+    /// returning `None` for any other kind of send error.
+    pub fn from_send_error<T>(items: usize, source: SendError) -> Option<Self> {
+        matches!(source.kind(), SendErrorKind::TooMuchData).then(|| BatchTooLarge {
+            items,
+            max_items: crate::max_batch_len::<T>(),
+            source,
+        })
+    }
+
+    /// Number of items in the rejected batch.
+    pub fn items(&self) -> usize {
+        self.items
+    }
+
+    /// Maximum number of items fitting into a single request.
+    pub fn max_items(&self) -> usize {
+        self.max_items
+    }
+
+    /// Original [`SendErrorKind::TooMuchData`] error this one was recognized
+    /// from, e.g. for code matching on [`SendError`] alone.
+    pub fn send_error(&self) -> SendError {
+        self.source
+    }
+}
+
+impl std::fmt::Display for BatchTooLarge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "batch of {} items is too large, split it into batches of at most {} items",
+            self.items, self.max_items
+        )
+    }
+}
+
+impl std::error::Error for BatchTooLarge {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Byte blob isn't a whole number of encoded items.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DecodeError {
+    pub(crate) len: usize,
+    pub(crate) item_size: usize,
+}
+
+impl DecodeError {
+    /// Length of the rejected blob in bytes.
+    pub fn blob_len(&self) -> usize {
+        self.len
+    }
+
+    /// Size of a single encoded item in bytes.
+    pub fn item_size(&self) -> usize {
+        self.item_size
+    }
+}
+
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "blob length {} is not a multiple of item size {}",
+            self.len, self.item_size
+        )
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Reply payload isn't a whole number of items of its operation, so it can't
+/// be decoded.
+///
+/// Never produced for a well behaved cluster, but reported instead of
+/// panicking on a truncated or corrupted reply.
+#[derive(Clone, Copy, Debug)]
+pub struct MalformedReply {
+    pub(crate) operation: crate::Operation,
+    pub(crate) source: DecodeError,
+}
+
+impl MalformedReply {
+    /// Report failure to decode reply to `operation`.
+    pub fn from_decode_error(operation: crate::Operation, source: DecodeError) -> Self {
+        MalformedReply { operation, source }
+    }
+
+    /// Operation of the request this reply is for.
+    pub fn operation(&self) -> crate::Operation {
+        self.operation
+    }
+
+    /// Length of the reply payload in bytes.
+    pub fn payload_len(&self) -> usize {
+        self.source.len
+    }
+}
+
+impl std::fmt::Display for MalformedReply {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "malformed reply of {} bytes to {:?}",
+            self.source.len, self.operation
+        )
+    }
+}
+
+impl std::error::Error for MalformedReply {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Byte slice length doesn't match the size of the decoded entity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FromBytesError {
+    pub(crate) len: usize,
+    pub(crate) expected: usize,
+}
+
+impl FromBytesError {
+    /// Length of the rejected slice in bytes.
+    pub fn slice_len(&self) -> usize {
+        self.len
+    }
+
+    /// Size of the entity in bytes.
+    pub fn expected(&self) -> usize {
+        self.expected
+    }
+}
+
+impl std::fmt::Display for FromBytesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "byte slice length {} doesn't match entity size {}",
+            self.len, self.expected
+        )
+    }
+}
+
+impl std::error::Error for FromBytesError {}
+
+/// Same id appears twice in a batch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DuplicateId {
+    pub(crate) id: u128,
+    pub(crate) first: usize,
+    pub(crate) second: usize,
+}
+
+impl DuplicateId {
+    /// Repeated id.
+    pub fn id(&self) -> u128 {
+        self.id
+    }
+
+    /// Index of the first occurrence of the id.
+    pub fn first(&self) -> usize {
+        self.first
+    }
+
+    /// Index of the second occurrence of the id.
+    pub fn second(&self) -> usize {
+        self.second
+    }
+}
+
+impl std::fmt::Display for DuplicateId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "id {} is repeated at indices {} and {}",
+            self.id, self.first, self.second
+        )
+    }
+}
+
+impl std::error::Error for DuplicateId {}
+
+/// Filter limit is zero or above [`Filter::MAX_LIMIT`].
+///
+/// [`Filter::MAX_LIMIT`]: crate::account::Filter::MAX_LIMIT
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FilterLimitError {
+    pub(crate) limit: u32,
+}
+
+impl FilterLimitError {
+    /// Rejected limit.
+    pub fn limit(&self) -> u32 {
+        self.limit
+    }
+}
+
+impl std::fmt::Display for FilterLimitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "filter limit {} is not within 1..={}",
+            self.limit,
+            crate::account::Filter::MAX_LIMIT
+        )
+    }
+}
+
+impl std::error::Error for FilterLimitError {}
+
+/// Transfer has mutually exclusive flags set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FlagConflict {
+    pub(crate) flags: crate::transfer::Flags,
+}
+
+impl FlagConflict {
+    /// Conflicting flags, only those which can't be set together.
+    pub fn flags(&self) -> crate::transfer::Flags {
+        self.flags
+    }
+}
+
+impl std::fmt::Display for FlagConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "transfer flags are mutually exclusive: {:?}", self.flags)
+    }
+}
+
+impl std::error::Error for FlagConflict {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn send_error_is_not_retriable() {
+        for code in sys_safe::MIN_PACKET_STATUS_ERROR_CODE..=sys_safe::MAX_PACKET_STATUS_ERROR_CODE
+        {
+            let error = SendError(NonZeroU8::new(code).unwrap());
+            assert!(!error.kind().is_retriable(), "{error:?}");
+            assert!(!error.is_retriable(), "{error:?}");
+        }
+        assert!(!SendErrorKind::UnstableUncategorized.is_retriable());
+        assert!(!SendError(NonZeroU8::MAX).is_retriable());
+    }
+
+    #[test]
+    fn chain_root() {
+        let api = CreateTransfersApiError::from_raw_results(vec![
+            sys::tb_create_transfers_result_t {
+                index: 0,
+                result: CreateTransferErrorKind::LinkedEventFailed as u32,
+            },
+            sys::tb_create_transfers_result_t {
+                index: 1,
+                result: CreateTransferErrorKind::ExceedsCredits as u32,
+            },
+        ])
+        .unwrap();
+        let root = api.chain_root().unwrap();
+        assert_eq!(root.index(), 1);
+        assert!(matches!(
+            root.kind(),
+            CreateTransferErrorKind::ExceedsCredits
+        ));
+        assert_eq!(api.root_causes().count(), 1);
+
+        let api = CreateAccountsApiError::from_raw_results(vec![
+            sys::tb_create_accounts_result_t {
+                index: 0,
+                result: CreateAccountErrorKind::LinkedEventFailed as u32,
+            },
+            sys::tb_create_accounts_result_t {
+                index: 1,
+                result: CreateAccountErrorKind::Exists as u32,
+            },
+        ])
+        .unwrap();
+        let root = api.chain_root().unwrap();
+        assert_eq!(root.index(), 1);
+        assert!(matches!(root.kind(), CreateAccountErrorKind::Exists));
+        assert_eq!(api.root_causes().count(), 1);
+
+        let api =
+            CreateAccountsApiError::from_raw_results(vec![sys::tb_create_accounts_result_t {
+                index: 0,
+                result: CreateAccountErrorKind::LinkedEventFailed as u32,
+            }])
+            .unwrap();
+        assert!(api.chain_root().is_none());
+    }
+
+    #[test]
+    fn into_failures() {
+        let api = CreateAccountsApiError::from_raw_results(vec![
+            sys::tb_create_accounts_result_t {
+                index: 1,
+                result: CreateAccountErrorKind::Exists as u32,
+            },
+            sys::tb_create_accounts_result_t {
+                index: 3,
+                result: CreateAccountErrorKind::LedgerMustNotBeZero as u32,
+            },
+        ])
+        .unwrap();
+        let failures = CreateAccountsError::Api(api).into_failures();
+        assert_eq!(failures.len(), 2);
+        assert_eq!(failures[0].0, 1);
+        assert!(matches!(failures[0].1, CreateAccountErrorKind::Exists));
+        assert_eq!(failures[1].0, 3);
+        assert!(matches!(
+            failures[1].1,
+            CreateAccountErrorKind::LedgerMustNotBeZero
+        ));
+
+        let send = SendError::from(SendErrorKind::InvalidDataSize);
+        let failures = CreateAccountsError::Send(send).into_failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].0, 0);
+        assert!(matches!(
+            failures[0].1,
+            CreateAccountErrorKind::UnstableUncategorized
+        ));
+    }
+}
@@ -12,6 +12,7 @@ where
     U: UserDataPtr,
 {
     pub(crate) raw: sys::tb_client_t,
+    pub(crate) cluster_id: u128,
     pub(crate) on_completion: &'a dyn Callbacks<UserDataPtr = U>,
 }
 
@@ -33,6 +34,17 @@ impl<'a, U> ClientHandle<'a, U>
 where
     U: UserDataPtr,
 {
+    /// Cluster id the client was created with.
+    pub fn cluster_id(&self) -> u128 {
+        self.cluster_id
+    }
+
+    /// Acquire a packet from the pool to submit `user_data` with.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AcquirePacketError`] if the pool is exhausted or the client
+    /// is shutting down. `user_data` is dropped in that case.
     pub fn acquire(
         self,
         user_data: U,
@@ -61,7 +73,14 @@ where
 
         let user_data = U::into_raw_const_ptr(user_data);
 
-        let raw = unsafe { impl_(self.raw, user_data.cast(), operation.0)? };
-        Ok(Packet { raw, handle: self })
+        match unsafe { impl_(self.raw, user_data.cast(), operation.0) } {
+            Ok(raw) => Ok(Packet { raw, handle: self }),
+            Err(e) => {
+                // SAFETY: packet wasn't acquired, so pointer was never handed
+                // out to tigerbeetle and is still owned here
+                drop(unsafe { U::from_raw_const_ptr(user_data) });
+                Err(e)
+            }
+        }
     }
 }
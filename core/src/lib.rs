@@ -1,5 +1,8 @@
 pub mod account;
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
 mod callback;
+pub mod decode;
 pub mod error;
 mod handle;
 mod packet;
@@ -16,15 +19,58 @@ pub use handle::ClientHandle;
 pub use packet::*;
 pub use transfer::Transfer;
 
+/// Maximum size of request data or reply payload in bytes.
+pub const MESSAGE_BODY_SIZE_MAX: usize = sys::MESSAGE_BODY_SIZE_MAX;
+/// Maximum number of accounts in a single request.
+pub const MAX_BATCH_ACCOUNTS: usize = max_batch_len::<Account>();
+/// Maximum number of transfers in a single request.
+pub const MAX_BATCH_TRANSFERS: usize = max_batch_len::<Transfer>();
+
+/// Tigerbeetle release this client is built from, e.g. `"0.15.3"`.
+///
+/// TigerBeetle requires client and cluster to have matching major versions,
+/// so log or compare this against the cluster release on deployment.
+/// `tb_client` doesn't expose a protocol version.
+pub const fn client_version() -> &'static str {
+    sys::TIGERBEETLE_VERSION
+}
+
+/// Maximum number of `T` items fitting into a single request or reply.
+pub const fn max_batch_len<T>() -> usize {
+    MESSAGE_BODY_SIZE_MAX / mem::size_of::<T>()
+}
+
+// Sizes of bound structures in tigerbeetle's wire format. The cluster doesn't
+// report its schema through `tb_client`, so this at least fails the build if
+// bindings were generated from a header of an incompatible tigerbeetle
+// release.
+const _: () = {
+    assert!(mem::size_of::<sys::tb_account_t>() == 128);
+    assert!(mem::size_of::<sys::tb_transfer_t>() == 128);
+    assert!(mem::size_of::<sys::tb_account_filter_t>() == 64);
+    assert!(mem::size_of::<sys::tb_account_balance_t>() == 128);
+    assert!(mem::size_of::<sys::tb_create_accounts_result_t>() == 8);
+    assert!(mem::size_of::<sys::tb_create_transfers_result_t>() == 8);
+};
+
 type OnCompletionRawFn =
     unsafe extern "C" fn(usize, sys::tb_client_t, *mut sys::tb_packet_t, *const u8, u32);
 
+/// Low level tigerbeetle client.
+///
+/// `tb_client_init` spawns a single thread per client, joined on drop by
+/// `tb_client_deinit`. Every callback is called from that thread, whatever
+/// `concurrency_max` is, as the latter only sizes the packet pool. The thread
+/// isn't spawned through [`std::thread`], so it has no Rust name; use the
+/// first callback to correlate it, e.g. by [`std::thread::current`].
 pub struct Client<F>
 where
     F: CallbacksPtr,
 {
     raw: sys::tb_client_t,
+    cluster_id: u128,
     on_completion: *const F::Target,
+    ctx: *mut callback::CompletionContext<F::Target>,
     marker: PhantomData<F>,
 }
 
@@ -35,6 +81,16 @@ impl<F> Client<F>
 where
     F: CallbacksPtr,
 {
+    /// Create a new client.
+    ///
+    /// `concurrency_max` is the number of packets preallocated by
+    /// `tb_client_init`. Acquiring more packets at once fails with
+    /// [`error::AcquirePacketErrorKind::ConcurrencyMaxExceeded`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NewClientErrorKind::ConcurrencyMaxInvalid`] if
+    /// `concurrency_max` is zero, without calling `tb_client_init`.
     pub fn with_callback<A>(
         cluster_id: u128,
         address: A,
@@ -73,9 +129,17 @@ where
     where
         A: AsRef<[u8]>,
     {
+        if concurrency_max == 0 {
+            return Err(NewClientErrorKind::ConcurrencyMaxInvalid.into());
+        }
+
         let on_completion_fn = callback::on_completion_raw_fn::<F::Target>;
         let on_completion = F::into_raw_const_ptr(on_completion);
-        let on_completion_ctx = sptr::Strict::expose_addr(on_completion);
+        let ctx = Box::into_raw(Box::new(callback::CompletionContext {
+            cluster_id,
+            on_completion,
+        }));
+        let on_completion_ctx = sptr::Strict::expose_addr(ctx.cast_const());
 
         unsafe fn raw_with_callback(
             cluster_id: u128,
@@ -115,19 +179,28 @@ where
                 ) {
                     Ok(x) => x,
                     Err(err) => {
+                        drop(Box::from_raw(ctx));
                         F::from_raw_const_ptr(on_completion);
                         return Err(err);
                     }
                 }
             },
+            cluster_id,
             on_completion,
+            ctx,
             marker: PhantomData,
         })
     }
 
+    /// Cluster id the client was created with.
+    pub fn cluster_id(&self) -> u128 {
+        self.cluster_id
+    }
+
     pub fn handle(&self) -> ClientHandle<'_, F::UserDataPtr> {
         ClientHandle {
             raw: self.raw,
+            cluster_id: self.cluster_id,
             on_completion: unsafe { &*self.on_completion },
         }
     }
@@ -141,6 +214,71 @@ where
     }
 }
 
+impl<'env, C> Client<&'env C>
+where
+    C: Callbacks,
+{
+    /// Create a client with callbacks borrowing local state and pass it to
+    /// `f`, similar to [`std::thread::scope`].
+    ///
+    /// Client is dropped before returning, even if `f` panics. Dropping
+    /// blocks in `tb_client_deinit` until no more callbacks can fire, so
+    /// `on_completion` is never called after this function returns. `f` only
+    /// gets a reference to the client, so the client can't be leaked with
+    /// [`mem::forget`] to skip deinitialization.
+    pub fn scoped<A, R>(
+        cluster_id: u128,
+        address: A,
+        concurrency_max: u32,
+        on_completion: &'env C,
+        f: impl FnOnce(&Self) -> R,
+    ) -> Result<R, NewClientError>
+    where
+        A: AsRef<[u8]>,
+        C::UserDataPtr: 'static,
+    {
+        // SAFETY: client is owned here and dropped before `'env` ends, and
+        // user data is 'static
+        let client = unsafe {
+            Client::with_callback_unchecked(cluster_id, address, concurrency_max, on_completion)?
+        };
+        Ok(f(&client))
+    }
+}
+
+impl<C, U> Client<Box<CallbacksFn<C, U>>>
+where
+    C: Fn(Packet<'_, U>, &[u8]) + Sync,
+    U: UserDataPtr,
+{
+    /// Create a new client calling `on_completion` closure on every reply.
+    ///
+    /// Shorthand for [`Self::with_callback`] with a boxed [`CallbacksFn`].
+    /// Closure is called from the tigerbeetle thread, hence it must be
+    /// `Sync`. It's dropped together with the client.
+    ///
+    /// To submit different kinds of user data through one client, pick
+    /// `Box<Box<dyn UserData + Send>>` as `U`.
+    pub fn with_closure<A>(
+        cluster_id: u128,
+        address: A,
+        concurrency_max: u32,
+        on_completion: C,
+    ) -> Result<Self, NewClientError>
+    where
+        A: AsRef<[u8]>,
+        C: 'static,
+        U: 'static,
+    {
+        Client::with_callback(
+            cluster_id,
+            address,
+            concurrency_max,
+            Box::new(CallbacksFn::new(on_completion)),
+        )
+    }
+}
+
 /// Blocks until all pending requests finish
 impl<F> Drop for Client<F>
 where
@@ -161,7 +299,107 @@ where
             }
             #[cfg(not(feature = "tokio-rt-multi-thread"))]
             sys::tb_client_deinit(self.raw);
+            drop(Box::from_raw(self.ctx));
             F::from_raw_const_ptr(self.on_completion);
         }
     }
 }
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use std::sync::mpsc;
+
+    use super::*;
+
+    struct UserData(Vec<u8>);
+
+    impl crate::UserData for UserData {
+        fn data(&self) -> &[u8] {
+            &self.0
+        }
+    }
+
+    #[test]
+    fn cluster_id() {
+        let (sender, receiver) = mpsc::channel();
+        let client = Client::with_closure(
+            42,
+            "3000",
+            1,
+            move |packet: Packet<'_, Box<UserData>>, _: &[u8]| {
+                sender.send(packet.client_handle().cluster_id()).unwrap();
+            },
+        )
+        .unwrap();
+        assert_eq!(client.cluster_id(), 42);
+        assert_eq!(client.handle().cluster_id(), 42);
+
+        client
+            .acquire(Box::new(UserData(vec![0; 8])), Operation::from_code(0))
+            .unwrap()
+            .submit();
+        assert_eq!(receiver.recv().unwrap(), 42);
+    }
+
+    #[test]
+    fn user_data_mut() {
+        let (sender, receiver) = mpsc::channel();
+        let client = Client::with_closure(
+            0,
+            "3000",
+            1,
+            move |mut packet: Packet<'_, Box<UserData>>, payload: &[u8]| {
+                assert_eq!(payload, [1, 2, 3]);
+                assert_eq!(packet.data(), [1, 2, 3]);
+                packet.user_data_mut().0.push(4);
+                sender.send(packet.into_user_data().0).unwrap();
+            },
+        )
+        .unwrap();
+
+        let mut packet = client
+            .acquire(Box::new(UserData(vec![0])), Operation::from_code(0))
+            .unwrap();
+        packet.user_data_mut().0 = vec![1, 2, 3];
+        packet.submit();
+        assert_eq!(receiver.recv().unwrap(), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn acquire_exhausted_pool() {
+        use std::sync::Arc;
+
+        struct Counted(#[allow(dead_code)] Arc<()>);
+
+        impl crate::UserData for Counted {
+            fn data(&self) -> &[u8] {
+                &[]
+            }
+        }
+
+        let client =
+            Client::with_closure(0, "3000", 1, |_: Packet<'_, Box<Counted>>, _: &[u8]| {}).unwrap();
+        let held = client
+            .acquire(Box::new(Counted(Arc::new(()))), Operation::from_code(0))
+            .unwrap();
+
+        let owner = Arc::new(());
+        let e = client
+            .acquire(Box::new(Counted(owner.clone())), Operation::from_code(0))
+            .err()
+            .unwrap();
+        assert!(matches!(
+            e.kind(),
+            error::AcquirePacketErrorKind::ConcurrencyMaxExceeded
+        ));
+        assert!(e.is_retriable());
+        // User data of the failed acquire is dropped
+        assert_eq!(Arc::strong_count(&owner), 1);
+
+        drop(held);
+        client
+            .acquire(Box::new(Counted(owner)), Operation::from_code(0))
+            .unwrap()
+            .submit();
+    }
+}
@@ -79,6 +79,9 @@ where
         unsafe { self.raw().user_data.cast::<U::Target>().as_ref().unwrap() }
     }
 
+    /// Mutable access to user data, e.g. to record completion metadata in
+    /// [`Callbacks::on_completion`](crate::Callbacks::on_completion) before
+    /// [`Self::into_user_data`].
     pub fn user_data_mut(&mut self) -> &mut U::Target
     where
         U: std::ops::DerefMut,
@@ -96,6 +99,20 @@ where
         self.user_data().data()
     }
 
+    /// Number of `T` items in [`Self::data`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` is zero-sized or data is not a whole number of `T` items.
+    #[track_caller]
+    pub fn data_len_items<T: bytemuck::Pod>(&self) -> usize {
+        let len = self.data().len();
+        let size = mem::size_of::<T>();
+        assert_ne!(size, 0, "item type must not be zero-sized");
+        assert_eq!(len % size, 0, "packet data is not a whole number of items");
+        len / size
+    }
+
     pub fn client_handle(&self) -> ClientHandle<'a, U> {
         self.handle
     }
@@ -137,16 +154,22 @@ where
 }
 
 impl Operation {
-    const CODE_RANGE: std::ops::RangeInclusive<u8> =
-        sys::generated_safe::MIN_OPERATION_CODE..=sys::generated_safe::MAX_OPERATION_CODE;
+    /// Operation with raw code `code`, including codes unknown to this
+    /// version. Submitting an unknown operation fails with
+    /// [`SendErrorKind::InvalidOperation`].
+    pub const fn from_code(code: u8) -> Self {
+        Operation(code)
+    }
+
+    /// Same as [`Self::kind`], but returns `None` instead of hidden
+    /// `OperationKind::UnstableUncategorized` variant for unknown codes.
+    pub fn known_kind(self) -> Option<OperationKind> {
+        OperationKind::from_code(self.0)
+    }
 
     pub fn kind(self) -> OperationKind {
-        if Self::CODE_RANGE.contains(&self.0) {
-            // SAFETY: We checked if it's in range right above
-            unsafe { std::mem::transmute(self.0) }
-        } else {
-            OperationKind::UnstableUncategorized
-        }
+        self.known_kind()
+            .unwrap_or(OperationKind::UnstableUncategorized)
     }
 
     pub fn code(self) -> u8 {
@@ -157,11 +180,10 @@ impl Operation {
 impl std::fmt::Debug for Operation {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut d = f.debug_tuple("Operation");
-        if Self::CODE_RANGE.contains(&self.0) {
-            d.field(&self.kind());
-        } else {
-            d.field(&self.0);
-        }
+        match self.known_kind() {
+            Some(kind) => d.field(&kind),
+            None => d.field(&self.0),
+        };
         d.finish()
     }
 }
@@ -169,10 +191,37 @@ impl std::fmt::Debug for Operation {
 impl From<OperationKind> for Operation {
     /// Panics on hidden `OperationKind::UnstableUncategorized` variant.
     fn from(value: OperationKind) -> Self {
-        let code = value as _;
-        if !Self::CODE_RANGE.contains(&code) {
-            panic!("OperationKind::{value:?}")
+        Operation(value.code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operation_kind_code_round_trip() {
+        let mut known = 0;
+        for code in 0..=u8::MAX {
+            let Some(kind) = OperationKind::from_code(code) else {
+                assert!(Operation::from_code(code).known_kind().is_none());
+                assert!(matches!(
+                    Operation::from_code(code).kind(),
+                    OperationKind::UnstableUncategorized
+                ));
+                continue;
+            };
+            known += 1;
+            assert_eq!(kind.code(), code);
+            assert_eq!(Operation::from(kind).code(), code);
+            assert_eq!(Operation::from_code(code).kind().code(), code);
         }
-        Operation(code)
+        assert_eq!(known, 7);
+    }
+
+    #[test]
+    #[should_panic]
+    fn operation_kind_hidden_variant_has_no_code() {
+        OperationKind::UnstableUncategorized.code();
     }
 }
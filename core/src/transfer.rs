@@ -1,7 +1,15 @@
-use std::time::{Duration, SystemTime};
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime},
+};
 
 use bytemuck::{Pod, TransparentWrapper, Zeroable};
 
+use crate::{
+    account::AccountId,
+    error::{CreateTransferErrorKind, DecodeError, DuplicateId, FlagConflict, FromBytesError},
+};
+
 pub use sys::generated_safe::TransferFlags as Flags;
 pub use sys::tb_transfer_t as Raw;
 
@@ -18,6 +26,9 @@ impl Transfer {
     pub const fn from_raw(raw: Raw) -> Self {
         Transfer(raw)
     }
+    pub fn from_raw_ref(raw: &Raw) -> &Self {
+        Self::wrap_ref(raw)
+    }
     pub const fn into_raw(self) -> Raw {
         self.0
     }
@@ -47,6 +58,26 @@ impl Transfer {
         self
     }
 
+    /// Same as [`Self::id`], but as [`uuid::Uuid`].
+    ///
+    /// Uuid bytes are the big-endian representation of the `u128` value, as
+    /// in [`uuid::Uuid::from_u128`].
+    #[cfg(feature = "uuid")]
+    pub fn id_uuid(&self) -> uuid::Uuid {
+        uuid::Uuid::from_u128(self.id())
+    }
+    #[cfg(feature = "uuid")]
+    #[track_caller]
+    pub fn set_id_uuid(&mut self, id: uuid::Uuid) {
+        self.set_id(id.as_u128());
+    }
+    #[cfg(feature = "uuid")]
+    #[track_caller]
+    pub fn with_id_uuid(mut self, id: uuid::Uuid) -> Self {
+        self.set_id_uuid(id);
+        self
+    }
+
     pub const fn debit_account_id(&self) -> u128 {
         self.0.debit_account_id
     }
@@ -58,6 +89,29 @@ impl Transfer {
         self
     }
 
+    /// Same as [`Self::debit_account_id`], but as [`uuid::Uuid`].
+    ///
+    /// Uuid bytes are the big-endian representation of the `u128` value, as
+    /// in [`uuid::Uuid::from_u128`].
+    #[cfg(feature = "uuid")]
+    pub fn debit_account_id_uuid(&self) -> uuid::Uuid {
+        uuid::Uuid::from_u128(self.debit_account_id())
+    }
+    #[cfg(feature = "uuid")]
+    #[track_caller]
+    pub fn set_debit_account_id_uuid(&mut self, debit_account_id: uuid::Uuid) {
+        self.set_debit_account_id(debit_account_id.as_u128());
+    }
+    #[cfg(feature = "uuid")]
+    #[track_caller]
+    pub fn with_debit_account_id_uuid(mut self, debit_account_id: uuid::Uuid) -> Self {
+        self.set_debit_account_id_uuid(debit_account_id);
+        self
+    }
+    pub const fn with_debit(self, debit_account_id: AccountId) -> Self {
+        self.with_debit_account_id(debit_account_id.0)
+    }
+
     pub const fn credit_account_id(&self) -> u128 {
         self.0.credit_account_id
     }
@@ -69,6 +123,29 @@ impl Transfer {
         self
     }
 
+    /// Same as [`Self::credit_account_id`], but as [`uuid::Uuid`].
+    ///
+    /// Uuid bytes are the big-endian representation of the `u128` value, as
+    /// in [`uuid::Uuid::from_u128`].
+    #[cfg(feature = "uuid")]
+    pub fn credit_account_id_uuid(&self) -> uuid::Uuid {
+        uuid::Uuid::from_u128(self.credit_account_id())
+    }
+    #[cfg(feature = "uuid")]
+    #[track_caller]
+    pub fn set_credit_account_id_uuid(&mut self, credit_account_id: uuid::Uuid) {
+        self.set_credit_account_id(credit_account_id.as_u128());
+    }
+    #[cfg(feature = "uuid")]
+    #[track_caller]
+    pub fn with_credit_account_id_uuid(mut self, credit_account_id: uuid::Uuid) -> Self {
+        self.set_credit_account_id_uuid(credit_account_id);
+        self
+    }
+    pub const fn with_credit(self, credit_account_id: AccountId) -> Self {
+        self.with_credit_account_id(credit_account_id.0)
+    }
+
     pub const fn user_data_128(&self) -> u128 {
         self.0.user_data_128
     }
@@ -80,6 +157,26 @@ impl Transfer {
         self
     }
 
+    /// Same as [`Self::user_data_128`], but as [`uuid::Uuid`].
+    ///
+    /// Uuid bytes are the big-endian representation of the `u128` value, as
+    /// in [`uuid::Uuid::from_u128`].
+    #[cfg(feature = "uuid")]
+    pub fn user_data_128_uuid(&self) -> uuid::Uuid {
+        uuid::Uuid::from_u128(self.user_data_128())
+    }
+    #[cfg(feature = "uuid")]
+    #[track_caller]
+    pub fn set_user_data_128_uuid(&mut self, user_data_128: uuid::Uuid) {
+        self.set_user_data_128(user_data_128.as_u128());
+    }
+    #[cfg(feature = "uuid")]
+    #[track_caller]
+    pub fn with_user_data_128_uuid(mut self, user_data_128: uuid::Uuid) -> Self {
+        self.set_user_data_128_uuid(user_data_128);
+        self
+    }
+
     pub const fn user_data_64(&self) -> u64 {
         self.0.user_data_64
     }
@@ -135,6 +232,26 @@ impl Transfer {
         self
     }
 
+    /// Same as [`Self::pending_id`], but as [`uuid::Uuid`].
+    ///
+    /// Uuid bytes are the big-endian representation of the `u128` value, as
+    /// in [`uuid::Uuid::from_u128`].
+    #[cfg(feature = "uuid")]
+    pub fn pending_id_uuid(&self) -> uuid::Uuid {
+        uuid::Uuid::from_u128(self.pending_id())
+    }
+    #[cfg(feature = "uuid")]
+    #[track_caller]
+    pub fn set_pending_id_uuid(&mut self, pending_id: uuid::Uuid) {
+        self.set_pending_id(pending_id.as_u128());
+    }
+    #[cfg(feature = "uuid")]
+    #[track_caller]
+    pub fn with_pending_id_uuid(mut self, pending_id: uuid::Uuid) -> Self {
+        self.set_pending_id_uuid(pending_id);
+        self
+    }
+
     pub const fn flags(&self) -> Flags {
         Flags::from_bits_retain(self.0.flags)
     }
@@ -162,17 +279,321 @@ impl Transfer {
     pub const fn amount(&self) -> u128 {
         self.0.amount
     }
+    /// Set amount verbatim.
+    ///
+    /// Debug builds panic on values looking like a negative integer cast to
+    /// `u128`, except for `u128::MAX` used with balancing and post pending
+    /// transfers. Use [`Self::checked_set_amount`] for signed amounts.
+    #[track_caller]
     pub fn set_amount(&mut self, amount: u128) {
-        self.0.amount = amount;
+        *self = self.with_amount(amount);
     }
+    /// Set amount verbatim. See [`Self::set_amount`].
+    #[track_caller]
     pub const fn with_amount(mut self, amount: u128) -> Self {
+        debug_assert!(
+            amount <= i128::MAX as u128 || amount == u128::MAX,
+            "transfer amount looks like a negative integer cast to `u128`"
+        );
         self.0.amount = amount;
         self
     }
+    /// Set amount from any integer type, failing on negative values instead
+    /// of wrapping them.
+    pub fn checked_set_amount<A>(&mut self, amount: A) -> Result<(), A::Error>
+    where
+        A: TryInto<u128>,
+    {
+        self.0.amount = amount.try_into()?;
+        Ok(())
+    }
+
+    /// Effect of this transfer on the account with `account_id`: `+amount`
+    /// if it's the credit account, `-amount` if it's the debit account.
+    ///
+    /// Returns `None` if the account is neither of them or if the amount
+    /// doesn't fit into `i128`.
+    pub fn signed_amount_for(&self, account_id: u128) -> Option<i128> {
+        let amount = i128::try_from(self.amount()).ok()?;
+        match (
+            account_id == self.credit_account_id(),
+            account_id == self.debit_account_id(),
+        ) {
+            (true, true) => Some(0),
+            (true, false) => Some(amount),
+            (false, true) => Some(-amount),
+            (false, false) => None,
+        }
+    }
 
     pub fn timestamp(&self) -> SystemTime {
         SystemTime::UNIX_EPOCH + Duration::from_nanos(self.0.timestamp)
     }
+    /// Nanoseconds since unix epoch.
+    pub const fn timestamp_nanos(&self) -> u64 {
+        self.0.timestamp
+    }
+    /// Moment pending transfer is voided if not posted or voided before,
+    /// [`Self::timestamp`] plus [`Self::timeout`] seconds.
+    ///
+    /// Returns `None` if transfer isn't [`Flags::PENDING`], has no timeout or
+    /// has no timestamp yet, which is zero until the cluster assigns it on
+    /// creation, so only transfers looked up from the cluster have expiry.
+    pub fn expiry(&self) -> Option<SystemTime> {
+        if !self.flags().contains(Flags::PENDING) || self.0.timeout == 0 || self.0.timestamp == 0 {
+            return None;
+        }
+        Some(self.timestamp() + Duration::from_secs(self.0.timeout.into()))
+    }
+
+    /// `timestamp_min` of [`Filter`](crate::account::Filter) requesting the
+    /// page of account transfers following this one.
+    ///
+    /// Filter bounds are inclusive, so it's one nanosecond past
+    /// [`Self::timestamp`] to not fetch this transfer again. Timestamps are
+    /// unique within a cluster, so no transfer is skipped either.
+    pub fn next_page_timestamp(&self) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_nanos(self.0.timestamp) + Duration::from_nanos(1)
+    }
+    /// Same as [`Self::timestamp`], but as [`chrono::DateTime`].
+    #[cfg(feature = "chrono")]
+    pub fn timestamp_chrono(&self) -> chrono::DateTime<chrono::Utc> {
+        crate::util::chrono_time::from_nanos(self.0.timestamp)
+    }
+}
+
+/// Builder of a linked chain of transfers, which succeed or fail together.
+///
+/// Sets [`Flags::LINKED`] on every transfer except the last one, and clears
+/// it on the last one, which would otherwise leave the chain open and fail
+/// it with [`CreateTransferErrorKind::LinkedEventChainOpen`].
+#[derive(Clone, Debug, Default)]
+pub struct LinkedTransfers(Vec<Transfer>);
+
+impl LinkedTransfers {
+    pub fn new() -> Self {
+        LinkedTransfers(Vec::new())
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        LinkedTransfers(Vec::with_capacity(capacity))
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(mut self, transfer: Transfer) -> Self {
+        self.push(transfer);
+        self
+    }
+
+    pub fn push(&mut self, transfer: Transfer) {
+        self.0.push(transfer);
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Get transfers with linked flags set up.
+    pub fn finish(mut self) -> Vec<Transfer> {
+        if let Some((last, rest)) = self.0.split_last_mut() {
+            for transfer in rest {
+                transfer.set_flags(transfer.flags() | Flags::LINKED);
+            }
+            last.set_flags(last.flags() - Flags::LINKED);
+        }
+        self.0
+    }
+}
+
+impl Extend<Transfer> for LinkedTransfers {
+    fn extend<I: IntoIterator<Item = Transfer>>(&mut self, iter: I) {
+        self.0.extend(iter);
+    }
+}
+
+impl FromIterator<Transfer> for LinkedTransfers {
+    fn from_iter<I: IntoIterator<Item = Transfer>>(iter: I) -> Self {
+        LinkedTransfers(Vec::from_iter(iter))
+    }
+}
+
+/// Conversion of domain types into transfers, see
+/// `Client::create_transfers_from` of the high level client.
+pub trait IntoTransfer {
+    fn into_transfer(self) -> Transfer;
+}
+
+impl IntoTransfer for Transfer {
+    fn into_transfer(self) -> Transfer {
+        self
+    }
+}
+
+/// Check transfers for problems tigerbeetle would reject them with, without
+/// sending anything.
+///
+/// Only problems detectable from the batch itself are reported: ids, flags,
+/// required fields and unterminated linked chain. Problems depending on the
+/// cluster state, like missing accounts or exceeded balances, are not.
+///
+/// # Errors
+///
+/// Returns every found problem along with the index of its transfer.
+pub fn validate_batch(transfers: &[Transfer]) -> Result<(), Vec<(usize, CreateTransferErrorKind)>> {
+    let mut problems = Vec::new();
+    for (i, transfer) in transfers.iter().enumerate() {
+        let mut problem = |kind| problems.push((i, kind));
+        transfer.validate(&mut problem);
+        if i + 1 == transfers.len() && transfer.flags().contains(Flags::LINKED) {
+            problem(CreateTransferErrorKind::LinkedEventChainOpen);
+        }
+    }
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(problems)
+    }
+}
+
+/// Check that no id is repeated within the batch, without sending anything.
+///
+/// Tigerbeetle would fail the repeated transfer with `Exists` or one of the
+/// `ExistsWith*` kinds. Opt-in, as it allocates a set of all ids.
+///
+/// # Errors
+///
+/// Returns [`DuplicateId`] with indices of the first repeated id.
+pub fn check_unique_ids(transfers: &[Transfer]) -> Result<(), DuplicateId> {
+    let mut seen = HashMap::with_capacity(transfers.len());
+    for (i, transfer) in transfers.iter().enumerate() {
+        if let Some(&first) = seen.get(&transfer.id()) {
+            return Err(DuplicateId {
+                id: transfer.id(),
+                first,
+                second: i,
+            });
+        }
+        seen.insert(transfer.id(), i);
+    }
+    Ok(())
+}
+
+/// Encode transfers as a single blob of their raw bytes, e.g. to persist
+/// them in an outbox exactly as they would be sent.
+pub fn serialize_batch(transfers: &[Transfer]) -> Vec<u8> {
+    bytemuck::cast_slice(transfers).to_vec()
+}
+
+/// Decode transfers from a blob produced by [`serialize_batch`].
+///
+/// # Errors
+///
+/// Returns [`DecodeError`] if `bytes` length is not a multiple of the size of
+/// [`Transfer`].
+pub fn deserialize_batch(bytes: &[u8]) -> Result<Vec<Transfer>, DecodeError> {
+    crate::decode::decode(bytes)
+}
+
+impl Transfer {
+    /// Check for flag combinations rejected by tigerbeetle with
+    /// [`FlagsAreMutuallyExclusive`], without a round trip to the cluster.
+    ///
+    /// Only checks flags. [`validate_batch`] checks every other field too.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FlagConflict`] listing the conflicting flags if more than one
+    /// of `PENDING`, `POST_PENDING_TRANSFER` and `VOID_PENDING_TRANSFER` is
+    /// set, or if posting or voiding is combined with balancing flags.
+    ///
+    /// [`FlagsAreMutuallyExclusive`]: CreateTransferErrorKind::FlagsAreMutuallyExclusive
+    pub fn validate_flags(&self) -> Result<(), FlagConflict> {
+        let flags = self.flags();
+        let post_or_void = Flags::POST_PENDING_TRANSFER | Flags::VOID_PENDING_TRANSFER;
+        let balancing = Flags::BALANCING_DEBIT | Flags::BALANCING_CREDIT;
+
+        let phase = flags & (Flags::PENDING | post_or_void);
+        let mut conflict = Flags::empty();
+        if phase.bits().count_ones() > 1 {
+            conflict |= phase;
+        }
+        if flags.intersects(post_or_void) && flags.intersects(balancing) {
+            conflict |= flags & (post_or_void | balancing);
+        }
+        if conflict.is_empty() {
+            Ok(())
+        } else {
+            Err(FlagConflict { flags: conflict })
+        }
+    }
+
+    fn validate(&self, mut problem: impl FnMut(CreateTransferErrorKind)) {
+        use CreateTransferErrorKind as K;
+
+        let t = &self.0;
+        let flags = self.flags();
+        if t.timestamp != 0 {
+            problem(K::TimestampMustBeZero);
+        }
+        if Flags::from_bits(t.flags).is_none() {
+            problem(K::ReservedFlag);
+        }
+        match t.id {
+            0 => problem(K::IdMustNotBeZero),
+            u128::MAX => problem(K::IdMustNotBeIntMax),
+            _ => {}
+        }
+
+        if self.validate_flags().is_err() {
+            problem(K::FlagsAreMutuallyExclusive);
+        }
+
+        if flags.intersects(Flags::POST_PENDING_TRANSFER | Flags::VOID_PENDING_TRANSFER) {
+            match t.pending_id {
+                0 => problem(K::PendingIdMustNotBeZero),
+                u128::MAX => problem(K::PendingIdMustNotBeIntMax),
+                id if id == t.id => problem(K::PendingIdMustBeDifferent),
+                _ => {}
+            }
+            if t.timeout != 0 {
+                problem(K::TimeoutReservedForPendingTransfer);
+            }
+            return;
+        }
+
+        match t.debit_account_id {
+            0 => problem(K::DebitAccountIdMustNotBeZero),
+            u128::MAX => problem(K::DebitAccountIdMustNotBeIntMax),
+            _ => {}
+        }
+        match t.credit_account_id {
+            0 => problem(K::CreditAccountIdMustNotBeZero),
+            u128::MAX => problem(K::CreditAccountIdMustNotBeIntMax),
+            _ => {}
+        }
+        if t.debit_account_id == t.credit_account_id {
+            problem(K::AccountsMustBeDifferent);
+        }
+        if t.pending_id != 0 {
+            problem(K::PendingIdMustBeZero);
+        }
+        if t.timeout != 0 && !flags.contains(Flags::PENDING) {
+            problem(K::TimeoutReservedForPendingTransfer);
+        }
+        if t.amount == 0 && !flags.intersects(Flags::BALANCING_DEBIT | Flags::BALANCING_CREDIT) {
+            problem(K::AmountMustNotBeZero);
+        }
+        if t.ledger == 0 {
+            problem(K::LedgerMustNotBeZero);
+        }
+        if t.code == 0 {
+            problem(K::CodeMustNotBeZero);
+        }
+    }
 }
 
 impl std::fmt::Debug for Transfer {
@@ -195,6 +616,16 @@ impl std::fmt::Debug for Transfer {
     }
 }
 
+/// Zeroed transfer.
+///
+/// Zero id is invalid, so set it with [`Transfer::set_id`] or [`Transfer::with_id`]
+/// before submission.
+impl Default for Transfer {
+    fn default() -> Self {
+        Transfer(Raw::zeroed())
+    }
+}
+
 impl From<Raw> for Transfer {
     fn from(value: Raw) -> Self {
         Transfer(value)
@@ -205,3 +636,266 @@ impl From<Transfer> for Raw {
         value.0
     }
 }
+
+/// Decodes the raw in-memory layout, e.g. `bytemuck::bytes_of(&x)`. Slice
+/// doesn't need to be aligned.
+impl TryFrom<&[u8]> for Transfer {
+    type Error = FromBytesError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        bytemuck::try_pod_read_unaligned(value).map_err(|_| FromBytesError {
+            len: value.len(),
+            expected: std::mem::size_of::<Self>(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem;
+
+    use super::*;
+
+    fn transfer(id: u128, debit_account_id: u128, credit_account_id: u128) -> Transfer {
+        Transfer::new(id)
+            .with_debit_account_id(debit_account_id)
+            .with_credit_account_id(credit_account_id)
+            .with_amount(10)
+            .with_ledger(1)
+            .with_code(1)
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn uuid_round_trip() {
+        let [id, debit, credit, user_data, pending] =
+            [1u8, 2, 3, 4, 5].map(|b| uuid::Uuid::from_bytes([b; 16]));
+        let transfer = Transfer::new(1)
+            .with_id_uuid(id)
+            .with_debit_account_id_uuid(debit)
+            .with_credit_account_id_uuid(credit)
+            .with_user_data_128_uuid(user_data)
+            .with_pending_id_uuid(pending);
+        assert_eq!(transfer.id_uuid(), id);
+        assert_eq!(transfer.debit_account_id_uuid(), debit);
+        assert_eq!(transfer.credit_account_id_uuid(), credit);
+        assert_eq!(transfer.user_data_128_uuid(), user_data);
+        assert_eq!(transfer.pending_id_uuid(), pending);
+        assert_eq!(transfer.id(), id.as_u128());
+        assert_eq!(transfer.pending_id(), pending.as_u128());
+    }
+
+    #[test]
+    fn from_raw_ref() {
+        let raw = Raw::zeroed();
+        assert!(std::ptr::eq(Transfer::from_raw_ref(&raw).as_raw(), &raw));
+    }
+
+    #[test]
+    fn default_is_zeroed() {
+        assert_eq!(
+            bytemuck::bytes_of(&Transfer::default()),
+            bytemuck::bytes_of(&Transfer::zeroed()),
+        );
+    }
+
+    #[test]
+    fn checked_set_amount() {
+        let mut t = transfer(1, 2, 3);
+        assert!(t.checked_set_amount(-1i64).is_err());
+        assert!(t.checked_set_amount(i128::MIN).is_err());
+        assert_eq!(t.amount(), 10);
+        t.checked_set_amount(5u8).unwrap();
+        assert_eq!(t.amount(), 5);
+        t.checked_set_amount(i128::MAX).unwrap();
+        assert_eq!(t.amount(), i128::MAX as u128);
+    }
+
+    #[test]
+    fn with_amount_edges() {
+        let t = transfer(1, 2, 3);
+        assert_eq!(t.with_amount(0).amount(), 0);
+        assert_eq!(t.with_amount(i128::MAX as u128).amount(), i128::MAX as u128);
+        // Balancing and post pending transfers use `u128::MAX`
+        assert_eq!(t.with_amount(u128::MAX).amount(), u128::MAX);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic = "negative integer"]
+    fn with_amount_above_i128_max() {
+        transfer(1, 2, 3).with_amount(i128::MAX as u128 + 1);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic = "negative integer"]
+    fn set_amount_wrapped_negative() {
+        transfer(1, 2, 3).set_amount(-1i128 as u128 - 1);
+    }
+
+    #[test]
+    fn signed_amount_for() {
+        let t = transfer(1, 2, 3);
+        assert_eq!(t.signed_amount_for(2), Some(-10));
+        assert_eq!(t.signed_amount_for(3), Some(10));
+        assert_eq!(t.signed_amount_for(4), None);
+    }
+
+    #[test]
+    fn signed_amount_for_self_transfer() {
+        let t = transfer(1, 2, 2);
+        assert_eq!(t.signed_amount_for(2), Some(0));
+    }
+
+    #[test]
+    fn serialize_round_trip() {
+        let batch = [transfer(1, 2, 3), transfer(4, 5, 6).with_user_data_64(7)];
+        let bytes = serialize_batch(&batch);
+        assert_eq!(bytes.len(), 2 * mem::size_of::<Transfer>());
+        let decoded = deserialize_batch(&bytes).unwrap();
+        assert_eq!(serialize_batch(&decoded), bytes);
+        assert!(deserialize_batch(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn deserialize_truncated() {
+        let bytes = serialize_batch(&[transfer(1, 2, 3), transfer(4, 5, 6)]);
+        let e = deserialize_batch(&bytes[..bytes.len() - 1]).unwrap_err();
+        assert_eq!(e.blob_len(), bytes.len() - 1);
+        assert_eq!(e.item_size(), mem::size_of::<Transfer>());
+
+        let e = Transfer::try_from(&bytes[..mem::size_of::<Transfer>() - 1]).unwrap_err();
+        assert_eq!(e.slice_len(), mem::size_of::<Transfer>() - 1);
+        assert_eq!(e.expected(), mem::size_of::<Transfer>());
+    }
+
+    #[test]
+    fn deserialize_unaligned() {
+        let bytes = serialize_batch(&[transfer(1, 2, 3)]);
+        let mut shifted = vec![0; bytes.len() + 1];
+        shifted[1..].copy_from_slice(&bytes);
+        let decoded = deserialize_batch(&shifted[1..]).unwrap();
+        assert_eq!(decoded[0].id(), 1);
+        assert_eq!(Transfer::try_from(&shifted[1..]).unwrap().id(), 1);
+    }
+
+    fn problems(transfers: &[Transfer]) -> Vec<(usize, u32)> {
+        validate_batch(transfers)
+            .unwrap_err()
+            .into_iter()
+            .map(|(i, kind)| (i, kind as u32))
+            .collect()
+    }
+
+    #[test]
+    fn validate_empty_batch() {
+        assert!(validate_batch(&[]).is_ok());
+    }
+
+    #[test]
+    fn validate_reports_every_bad_entry() {
+        use CreateTransferErrorKind as K;
+
+        let mut zero_id = transfer(1, 2, 3);
+        zero_id.as_raw_mut().id = 0;
+        let batch = [
+            transfer(1, 2, 3),
+            zero_id,
+            transfer(4, 5, 5),
+            transfer(6, 7, 8).with_ledger(0).with_code(0),
+        ];
+        assert_eq!(
+            problems(&batch),
+            [
+                (1, K::IdMustNotBeZero as u32),
+                (2, K::AccountsMustBeDifferent as u32),
+                (3, K::LedgerMustNotBeZero as u32),
+                (3, K::CodeMustNotBeZero as u32),
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_linked_chain_at_end() {
+        use CreateTransferErrorKind as K;
+
+        let batch = [
+            transfer(1, 2, 3).with_flags(Flags::LINKED),
+            transfer(4, 5, 6).with_flags(Flags::LINKED),
+        ];
+        assert_eq!(problems(&batch), [(1, K::LinkedEventChainOpen as u32)]);
+
+        let closed = LinkedTransfers::from_iter(batch).finish();
+        assert!(validate_batch(&closed).is_ok());
+    }
+
+    #[test]
+    fn unique_ids() {
+        let batch = [transfer(1, 2, 3), transfer(4, 5, 6), transfer(7, 8, 9)];
+        assert!(check_unique_ids(&batch).is_ok());
+        assert!(check_unique_ids(&[]).is_ok());
+    }
+
+    #[test]
+    fn duplicate_pair() {
+        let batch = [transfer(1, 2, 3), transfer(4, 5, 6), transfer(1, 8, 9)];
+        let e = check_unique_ids(&batch).unwrap_err();
+        assert_eq!((e.id(), e.first(), e.second()), (1, 0, 2));
+    }
+
+    #[test]
+    fn duplicate_three_way_reports_first_repeat() {
+        let batch = [
+            transfer(4, 5, 6),
+            transfer(1, 2, 3),
+            transfer(1, 2, 3),
+            transfer(1, 2, 3),
+        ];
+        let e = check_unique_ids(&batch).unwrap_err();
+        assert_eq!((e.id(), e.first(), e.second()), (1, 1, 2));
+    }
+
+    #[test]
+    fn duplicate_zero_id() {
+        let batch = [Transfer::default(), transfer(1, 2, 3), Transfer::default()];
+        let e = check_unique_ids(&batch).unwrap_err();
+        assert_eq!((e.id(), e.first(), e.second()), (0, 0, 2));
+    }
+
+    #[test]
+    fn linked_empty() {
+        assert!(LinkedTransfers::new().finish().is_empty());
+    }
+
+    #[test]
+    fn linked_single_is_not_linked() {
+        let chain = LinkedTransfers::new()
+            .add(transfer(1, 2, 3).with_flags(Flags::LINKED | Flags::PENDING))
+            .finish();
+        assert_eq!(chain.len(), 1);
+        assert_eq!(chain[0].flags(), Flags::PENDING);
+    }
+
+    #[test]
+    fn linked_chain() {
+        let chain = LinkedTransfers::new()
+            .add(transfer(1, 2, 3))
+            .add(transfer(4, 5, 6).with_flags(Flags::LINKED))
+            .add(transfer(7, 8, 9).with_flags(Flags::PENDING))
+            .add(transfer(10, 11, 12).with_flags(Flags::LINKED))
+            .finish();
+        let flags: Vec<_> = chain.iter().map(Transfer::flags).collect();
+        assert_eq!(
+            flags,
+            [
+                Flags::LINKED,
+                Flags::LINKED,
+                Flags::LINKED | Flags::PENDING,
+                Flags::empty(),
+            ]
+        );
+        let ids: Vec<_> = chain.iter().map(Transfer::id).collect();
+        assert_eq!(ids, [1, 4, 7, 10]);
+    }
+}
@@ -1,5 +1,8 @@
 //! Helpful abstractions to generalize over various types
 
+#[cfg(feature = "chrono")]
+pub(crate) mod chrono_time;
+pub mod id;
 mod owned_slice;
 mod raw_const_ptr;
 pub mod send_marker;
@@ -0,0 +1,20 @@
+use chrono::{DateTime, Utc};
+
+/// Convert tigerbeetle timestamp to [`DateTime`]. Exact for any `u64`.
+pub(crate) fn from_nanos(nanos: u64) -> DateTime<Utc> {
+    let secs = (nanos / 1_000_000_000) as i64;
+    let subsec_nanos = (nanos % 1_000_000_000) as u32;
+    DateTime::from_timestamp(secs, subsec_nanos)
+        .expect("any `u64` nanoseconds since unix epoch fit into `DateTime`")
+}
+
+/// Convert [`DateTime`] to tigerbeetle timestamp.
+///
+/// Leap second is clamped to the last nanosecond of the preceding second.
+/// Returns `None` for times before unix epoch or not fitting into `u64`.
+pub(crate) fn to_nanos(time: DateTime<Utc>) -> Option<u64> {
+    let secs = u64::try_from(time.timestamp()).ok()?;
+    let subsec_nanos = time.timestamp_subsec_nanos().min(999_999_999);
+    secs.checked_mul(1_000_000_000)?
+        .checked_add(subsec_nanos.into())
+}
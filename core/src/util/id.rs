@@ -0,0 +1,70 @@
+//! Helpers to derive account and transfer ids.
+
+const FNV_OFFSET_BASIS: u128 = 0x6c62272e07bb014262b821756295c58d;
+const FNV_PRIME: u128 = 0x0000000001000000000000000000013b;
+
+/// Derive a transfer id from an application idempotency key.
+///
+/// Same key always yields the same id, on any platform and any version of
+/// this crate, so a retried request resubmits the same transfer and
+/// tigerbeetle rejects the duplicate with
+/// [`Exists`](crate::error::CreateTransferErrorKind::Exists).
+///
+/// Id is the 128-bit FNV-1a hash of the key, moved away from the reserved
+/// `0` and `u128::MAX` values.
+///
+/// Hash is not cryptographic: keys controlled by an adversary may be crafted
+/// to collide.
+pub const fn from_idempotency_key(key: &[u8]) -> u128 {
+    let mut hash = FNV_OFFSET_BASIS;
+    let mut i = 0;
+    while i < key.len() {
+        hash ^= key[i] as u128;
+        hash = hash.wrapping_mul(FNV_PRIME);
+        i += 1;
+    }
+    avoid_reserved(hash)
+}
+
+/// Move `0` and `u128::MAX`, reserved by tigerbeetle, to neighbouring ids.
+const fn avoid_reserved(id: u128) -> u128 {
+    match id {
+        0 => 1,
+        u128::MAX => u128::MAX - 1,
+        id => id,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_keys() {
+        // FNV-1a 128-bit test vectors
+        assert_eq!(from_idempotency_key(b""), FNV_OFFSET_BASIS);
+        assert_eq!(
+            from_idempotency_key(b"a"),
+            0xd228cb696f1a8caf78912b704e4a8964
+        );
+        assert_eq!(
+            from_idempotency_key(b"foobar"),
+            0x343e1662793c64bf6f0d3597ba446f18
+        );
+        assert_eq!(
+            from_idempotency_key(b"transfer-42"),
+            0xc791015e4e2d3e295fdcabff5d708107
+        );
+    }
+
+    #[test]
+    fn never_reserved() {
+        assert_eq!(avoid_reserved(0), 1);
+        assert_eq!(avoid_reserved(u128::MAX), u128::MAX - 1);
+        assert_eq!(avoid_reserved(42), 42);
+        for i in 0..=u8::MAX {
+            let id = from_idempotency_key(&[i]);
+            assert!(id != 0 && id != u128::MAX);
+        }
+    }
+}
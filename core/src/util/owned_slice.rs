@@ -157,6 +157,46 @@ impl<T> From<Vec<T>> for SendOwnedSlice<T> {
     }
 }
 
+/// Moves elements into a single allocation of exactly `N` elements, so
+/// `client.create_transfers([t1, t2])` works without `.to_vec()`.
+///
+/// ```
+/// use tigerbeetle_unofficial_core::{util::SendOwnedSlice, Transfer};
+///
+/// let ids = SendOwnedSlice::from([1u128, 2]);
+/// assert_eq!(ids.as_slice(), [1, 2]);
+///
+/// let transfers: SendOwnedSlice<Transfer> = [Transfer::new(1), Transfer::new(2)].into();
+/// assert_eq!(transfers.len(), 2);
+/// assert_eq!(transfers.as_slice()[1].id(), 2);
+/// ```
+impl<T, const N: usize> From<[T; N]> for SendOwnedSlice<T> {
+    fn from(value: [T; N]) -> Self {
+        Vec::from(value).into()
+    }
+}
+
+/// Collects into a `Vec<T>` first, so a batch can be built with
+/// `iter.collect()` and passed to a client right away.
+///
+/// ```
+/// use tigerbeetle_unofficial_core::{util::SendOwnedSlice, Account};
+///
+/// let accounts: SendOwnedSlice<Account> = (1..=3).map(|id| Account::new(id, 1, 1)).collect();
+/// assert_eq!(accounts.len(), 3);
+/// assert_eq!(accounts.as_slice()[2].id(), 3);
+/// ```
+impl<T> FromIterator<T> for SendOwnedSlice<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Vec::from_iter(iter).into()
+    }
+}
+
+/// Drop rebuilds the same fat pointer `P::into_raw_const_ptr` returned, from
+/// its data pointer and length, so `P::from_raw_const_ptr` gets back exactly
+/// what it handed out. For `Arc<[T]>` that's the pointer past the refcount
+/// header, which `Arc::from_raw` offsets back from using the layout of `[T]`
+/// of that length.
 impl<P, T> From<P> for SendOwnedSlice<T>
 where
     P: RawConstPtr<Target = [T]> + Send + 'static,
@@ -238,6 +278,76 @@ where
     }
 }
 
+impl<'a, T, S> IntoIterator for &'a OwnedSlice<T, S>
+where
+    S: SendMarker,
+{
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
+/// Iterates over elements by value. Requires `T: Copy` since backing container
+/// might be shared, like `Arc<[T]>`, so elements are copied out of it instead
+/// of moved. Container is dropped with the iterator.
+impl<T, S> IntoIterator for OwnedSlice<T, S>
+where
+    T: Copy,
+    S: SendMarker,
+{
+    type Item = T;
+    type IntoIter = IntoIter<T, S>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            slice: self,
+            index: 0,
+        }
+    }
+}
+
+/// By value iterator over [`OwnedSlice`] elements.
+pub struct IntoIter<T, S = send_marker::Unsendable>
+where
+    S: SendMarker,
+{
+    slice: OwnedSlice<T, S>,
+    index: usize,
+}
+
+impl<T, S> Iterator for IntoIter<T, S>
+where
+    T: Copy,
+    S: SendMarker,
+{
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = *self.slice.as_slice().get(self.index)?;
+        self.index += 1;
+        Some(item)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.slice.len() - self.index;
+        (len, Some(len))
+    }
+}
+
+impl<T, S> ExactSizeIterator for IntoIter<T, S>
+where
+    T: Copy,
+    S: SendMarker,
+{
+}
+
 impl<S> AsRef<[u8]> for AsBytesOwnedSlice<S>
 where
     S: SendMarker,
@@ -254,22 +364,83 @@ where
 {
     #[inline]
     pub fn as_bytes(&self) -> &[u8] {
-        unsafe {
-            NonNull::slice_from_raw_parts(
-                self.owner.ptr.cast(),
-                self.size_of_element * self.owner.ctx.len,
-            )
-            .as_ref()
-        }
+        unsafe { NonNull::slice_from_raw_parts(self.owner.ptr.cast(), self.byte_len()).as_ref() }
     }
 
+    /// Number of elements, not bytes. See [`Self::byte_len`].
     #[inline]
     pub fn len(&self) -> usize {
         self.owner.len()
     }
 
+    /// Number of bytes, same as `self.as_bytes().len()`.
+    ///
+    /// ```
+    /// use tigerbeetle_unofficial_core::util::SendOwnedSlice;
+    ///
+    /// let bytes = SendOwnedSlice::from(vec![1u128, 2, 3]).into_as_bytes();
+    /// assert_eq!(bytes.len(), 3);
+    /// assert_eq!(bytes.byte_len(), 3 * 16);
+    /// assert_eq!(bytes.byte_len(), bytes.as_bytes().len());
+    /// ```
+    #[inline]
+    pub fn byte_len(&self) -> usize {
+        self.size_of_element * self.owner.len()
+    }
+
     #[inline]
     pub fn is_empty(&self) -> bool {
         self.owner.is_empty()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use super::*;
+
+    struct CountDrops(&'static AtomicUsize);
+
+    impl Drop for CountDrops {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn arc_slice_is_released_once() {
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+        let arc: Arc<[CountDrops]> = Arc::from([CountDrops(&DROPS), CountDrops(&DROPS)]);
+
+        let slice = SendOwnedSlice::from(Arc::clone(&arc));
+        assert_eq!(Arc::strong_count(&arc), 2);
+        assert!(std::ptr::eq(slice.as_slice(), &*arc));
+
+        drop(slice);
+        assert_eq!(Arc::strong_count(&arc), 1);
+        assert_eq!(DROPS.load(Ordering::Relaxed), 0);
+
+        drop(arc);
+        assert_eq!(DROPS.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn erased_arc_slice_is_released_once() {
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+        let arc: Arc<[CountDrops]> = Arc::from([CountDrops(&DROPS)]);
+
+        let erased = SendOwnedSlice::from(Arc::clone(&arc)).erase_type();
+        assert_eq!(Arc::strong_count(&arc), 2);
+        drop(erased);
+        assert_eq!(Arc::strong_count(&arc), 1);
+
+        let last = SendOwnedSlice::from(arc);
+        assert_eq!(DROPS.load(Ordering::Relaxed), 0);
+        drop(last);
+        assert_eq!(DROPS.load(Ordering::Relaxed), 1);
+    }
+}
@@ -0,0 +1,38 @@
+use tigerbeetle_unofficial as tb;
+
+// Pushes transfers one by one while the batcher submits them in full batches
+#[pollster::main]
+async fn main() {
+    let address = std::env::var("TB_ADDRESS");
+    let address = address.as_deref().unwrap_or("3000");
+    let client = tb::Client::new(0, address, 32).expect("creating a tigerbeetle client");
+
+    let accounts = [tb::Account::new(1, 777, 2), tb::Account::new(2, 777, 2)];
+    client
+        .create_accounts(accounts)
+        .await
+        .expect("creating accounts");
+
+    let mut batcher = tb::TransferBatcher::new(&client);
+    for id in 1..=100_000 {
+        let transfer = tb::Transfer::new(id)
+            .with_debit_account_id(accounts[0].id())
+            .with_credit_account_id(accounts[1].id())
+            .with_code(2)
+            .with_ledger(777)
+            .with_amount(1);
+        batcher.push(transfer).await.expect("sending transfers");
+    }
+    batcher.flush().await.expect("sending transfers");
+
+    for (offset, errors) in batcher.take_api_errors() {
+        for e in errors.as_slice() {
+            println!(
+                "transfer #{} failed: {}",
+                offset + u64::from(e.index()),
+                e.inner()
+            );
+        }
+    }
+    println!("Transfers created");
+}
@@ -2,8 +2,6 @@ use std::time::{Duration, Instant};
 
 use tigerbeetle_unofficial as tb;
 
-const MAX_MESSAGE_BYTE_SIZE: usize = (1024 * 1024) - 256;
-
 // Crate is runtime agnostic, so you can use tokio or any other async runtime
 #[pollster::main]
 async fn main() {
@@ -19,7 +17,7 @@ async fn main() {
 
     let accounts = [tb::Account::new(1, 777, 2), tb::Account::new(2, 777, 2)];
     client
-        .create_accounts(accounts.to_vec())
+        .create_accounts(accounts)
         .await
         .expect("creating accounts");
     println!("Accounts created successfully");
@@ -30,7 +28,7 @@ async fn main() {
 
     println!("Creating transfers...");
     const MAX_BATCHES: usize = 100;
-    const TRANSFERS_PER_BATCH: usize = MAX_MESSAGE_BYTE_SIZE / std::mem::size_of::<tb::Transfer>();
+    const TRANSFERS_PER_BATCH: usize = tb::MAX_BATCH_TRANSFERS;
     let max_batches = std::env::var("TIGERBEETLE_RS_MAX_BATCHES")
         .ok()
         .and_then(|s| s.parse().ok())
@@ -86,7 +84,7 @@ async fn main() {
     println!("Looking up accounts ...");
     let ids = accounts.map(|a| a.id());
     let accounts = client
-        .lookup_accounts(ids.to_vec())
+        .lookup_accounts(ids)
         .await
         .expect("looking up accounts");
     assert!(!accounts.is_empty());
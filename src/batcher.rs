@@ -0,0 +1,107 @@
+use std::mem;
+
+use crate::{
+    core,
+    error::{CreateTransfersApiError, RequestError},
+    Client, Transfer, MAX_BATCH_TRANSFERS,
+};
+
+/// Accumulates transfers and submits them in full batches.
+///
+/// Batches are submitted one at a time, each waiting for a free concurrency
+/// permit of the client like any other request, so several batchers share
+/// the client's concurrency. Api errors are collected instead of stopping
+/// the ingestion, see [`Self::take_api_errors`].
+pub struct TransferBatcher<'a> {
+    client: &'a Client,
+    buffer: Vec<Transfer>,
+    batch_len: usize,
+    /// Number of transfers already submitted.
+    submitted: u64,
+    api_errors: Vec<(u64, CreateTransfersApiError)>,
+}
+
+impl<'a> TransferBatcher<'a> {
+    /// Create batcher submitting batches of [`MAX_BATCH_TRANSFERS`] transfers.
+    pub fn new(client: &'a Client) -> Self {
+        TransferBatcher {
+            client,
+            buffer: Vec::new(),
+            batch_len: MAX_BATCH_TRANSFERS,
+            submitted: 0,
+            api_errors: Vec::new(),
+        }
+    }
+
+    pub fn batch_len(&self) -> usize {
+        self.batch_len
+    }
+    #[track_caller]
+    pub fn with_batch_len(mut self, batch_len: usize) -> Self {
+        assert_ne!(batch_len, 0, "batch_len must not be zero");
+        assert!(
+            batch_len <= MAX_BATCH_TRANSFERS,
+            "batch_len must not exceed `MAX_BATCH_TRANSFERS`"
+        );
+        self.batch_len = batch_len;
+        self
+    }
+
+    /// Number of buffered transfers not submitted yet.
+    pub fn pending(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Buffer `transfer`, submitting the batch once it's full.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RequestError`] if the full batch couldn't be sent or its
+    /// reply couldn't be decoded. Batch is dropped in that case.
+    pub async fn push(&mut self, transfer: Transfer) -> Result<(), RequestError> {
+        self.buffer.push(transfer);
+        if self.buffer.len() >= self.batch_len {
+            self.flush().await?;
+        }
+        Ok(())
+    }
+
+    /// Submit buffered transfers, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RequestError`] if the batch couldn't be sent or its reply
+    /// couldn't be decoded. Batch is dropped in that case.
+    pub async fn flush(&mut self) -> Result<(), RequestError> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        let batch = mem::replace(&mut self.buffer, Vec::with_capacity(self.batch_len));
+        let offset = self.submitted;
+        self.submitted += batch.len() as u64;
+        let res = self
+            .client
+            .submit(
+                core::util::SendOwnedSlice::from(batch).into_as_bytes(),
+                core::OperationKind::CreateTransfers.into(),
+            )
+            .await?
+            .into_create_transfers()?;
+        if let Err(e) = res {
+            self.api_errors.push((offset, e));
+        }
+        Ok(())
+    }
+
+    /// Api errors collected so far along with the number of transfers pushed
+    /// before the failed batch. Add it to the error's index to get the index
+    /// of the failed transfer among all pushed ones.
+    pub fn api_errors(&self) -> &[(u64, CreateTransfersApiError)] {
+        &self.api_errors
+    }
+
+    /// Take collected api errors, see [`Self::api_errors`].
+    pub fn take_api_errors(&mut self) -> Vec<(u64, CreateTransfersApiError)> {
+        mem::take(&mut self.api_errors)
+    }
+}
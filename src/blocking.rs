@@ -0,0 +1,126 @@
+//! Synchronous client for code without an async runtime.
+//!
+//! Every method blocks the current thread until tigerbeetle replies. Replies
+//! are delivered from the tigerbeetle thread, so no runtime is needed to
+//! drive requests to completion.
+
+use std::{
+    future::Future,
+    pin::pin,
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+};
+
+use crate::{
+    account,
+    core::util::{RawConstPtr, SendOwnedSlice},
+    error::{CreateAccountsError, CreateTransfersError, NewClientError, RequestError},
+    Account, Transfer,
+};
+
+/// Blocking wrapper around async [`crate::Client`].
+pub struct Client(crate::Client);
+
+impl Client {
+    /// Create a new client, see [`crate::Client::new`].
+    pub fn new<A>(
+        cluster_id: u128,
+        address: A,
+        concurrency_max: u32,
+    ) -> Result<Self, NewClientError>
+    where
+        A: AsRef<[u8]>,
+    {
+        crate::Client::new(cluster_id, address, concurrency_max).map(Client)
+    }
+
+    pub fn as_async(&self) -> &crate::Client {
+        &self.0
+    }
+
+    pub fn into_async(self) -> crate::Client {
+        self.0
+    }
+
+    pub fn create_accounts<T>(&self, accounts: T) -> Result<(), CreateAccountsError>
+    where
+        T: Into<SendOwnedSlice<Account>>,
+    {
+        block_on(self.0.create_accounts(accounts))
+    }
+
+    pub fn create_transfers<T>(&self, transfers: T) -> Result<(), CreateTransfersError>
+    where
+        T: Into<SendOwnedSlice<Transfer>>,
+    {
+        block_on(self.0.create_transfers(transfers))
+    }
+
+    pub fn get_account_balances<T>(&self, filter: T) -> Result<Vec<account::Balance>, RequestError>
+    where
+        T: RawConstPtr<Target = account::Filter> + Send + 'static,
+    {
+        block_on(self.0.get_account_balances(filter))
+    }
+
+    pub fn get_account_transfers<T>(&self, filter: T) -> Result<Vec<Transfer>, RequestError>
+    where
+        T: RawConstPtr<Target = account::Filter> + Send + 'static,
+    {
+        block_on(self.0.get_account_transfers(filter))
+    }
+
+    pub fn lookup_account(&self, id: u128) -> Result<Option<Account>, RequestError> {
+        block_on(self.0.lookup_account(id))
+    }
+
+    pub fn lookup_accounts<T>(&self, ids: T) -> Result<Vec<Account>, RequestError>
+    where
+        T: Into<SendOwnedSlice<u128>>,
+    {
+        block_on(self.0.lookup_accounts(ids))
+    }
+
+    pub fn lookup_transfer(&self, id: u128) -> Result<Option<Transfer>, RequestError> {
+        block_on(self.0.lookup_transfer(id))
+    }
+
+    pub fn lookup_transfers<T>(&self, ids: T) -> Result<Vec<Transfer>, RequestError>
+    where
+        T: Into<SendOwnedSlice<u128>>,
+    {
+        block_on(self.0.lookup_transfers(ids))
+    }
+}
+
+impl From<crate::Client> for Client {
+    fn from(value: crate::Client) -> Self {
+        Client(value)
+    }
+}
+
+/// Unparks the blocked thread when the reply arrives.
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
@@ -0,0 +1,254 @@
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
+
+use crate::{
+    error::{BuildClientError, ConfigError, ConfigErrorKind},
+    Callbacks, Client, ReplyMeta, MAX_CONCURRENCY,
+};
+
+/// What create requests do with an empty batch.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EmptyBatchBehavior {
+    /// Succeed without sending anything.
+    #[default]
+    NoOp,
+    /// Fail with [`SendErrorKind::InvalidDataSize`] without sending anything.
+    ///
+    /// [`SendErrorKind::InvalidDataSize`]: crate::error::SendErrorKind::InvalidDataSize
+    Error,
+}
+
+/// Builder of [`Client`] validating the whole configuration at once.
+#[derive(Clone)]
+pub struct ClientBuilder {
+    cluster_id: u128,
+    address: Vec<u8>,
+    concurrency_max: u32,
+    empty_batch_behavior: EmptyBatchBehavior,
+    on_first_completion_thread: Option<Arc<dyn Fn() + Send + Sync>>,
+    on_reply: Option<Arc<dyn Fn(ReplyMeta) + Send + Sync>>,
+}
+
+impl ClientBuilder {
+    pub fn new<A>(cluster_id: u128, address: A, concurrency_max: u32) -> Self
+    where
+        A: AsRef<[u8]>,
+    {
+        ClientBuilder {
+            cluster_id,
+            address: address.as_ref().to_vec(),
+            concurrency_max,
+            empty_batch_behavior: EmptyBatchBehavior::default(),
+            on_first_completion_thread: None,
+            on_reply: None,
+        }
+    }
+
+    pub fn cluster_id(&self) -> u128 {
+        self.cluster_id
+    }
+    pub fn with_cluster_id(mut self, cluster_id: u128) -> Self {
+        self.cluster_id = cluster_id;
+        self
+    }
+
+    pub fn address(&self) -> &[u8] {
+        &self.address
+    }
+    pub fn with_address<A>(mut self, address: A) -> Self
+    where
+        A: AsRef<[u8]>,
+    {
+        self.address = address.as_ref().to_vec();
+        self
+    }
+
+    pub fn concurrency_max(&self) -> u32 {
+        self.concurrency_max
+    }
+    pub fn with_concurrency_max(mut self, concurrency_max: u32) -> Self {
+        self.concurrency_max = concurrency_max;
+        self
+    }
+
+    pub fn empty_batch_behavior(&self) -> EmptyBatchBehavior {
+        self.empty_batch_behavior
+    }
+    pub fn with_empty_batch_behavior(mut self, empty_batch_behavior: EmptyBatchBehavior) -> Self {
+        self.empty_batch_behavior = empty_batch_behavior;
+        self
+    }
+
+    /// Run `hook` once on the tigerbeetle completion thread, before the first
+    /// reply is dispatched.
+    ///
+    /// Client has a single completion thread spawned by `tb_client`, not by
+    /// Rust, so it has no name. Use this hook to correlate it in profiles,
+    /// e.g. by logging [`std::thread::current`] id, to set thread-local state
+    /// or to register the thread with a profiler.
+    ///
+    /// If `hook` panics, the panic is caught after being reported and the
+    /// hook isn't called again. Replies are delivered as usual.
+    pub fn on_first_completion_thread<F>(mut self, hook: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.on_first_completion_thread = Some(Arc::new(hook));
+        self
+    }
+
+    /// Call `hook` on the tigerbeetle completion thread for every reply,
+    /// before it is decoded and sent to the awaiting task.
+    ///
+    /// `tb_client` passes only the reply payload to the completion callback,
+    /// without the message header, so there is no server commit timestamp to
+    /// report. [`ReplyMeta::received_at`] is taken on the client instead.
+    /// Hook blocks delivery of every reply, so keep it cheap.
+    ///
+    /// If `hook` panics, the panic is caught after being reported and the
+    /// reply is delivered as usual. Hook is still called for later replies.
+    pub fn on_reply<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(ReplyMeta) + Send + Sync + 'static,
+    {
+        self.on_reply = Some(Arc::new(hook));
+        self
+    }
+
+    /// Check configuration for problems without creating a client.
+    ///
+    /// # Errors
+    ///
+    /// Returns every found problem at once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tigerbeetle_unofficial::{error::ConfigErrorKind, ClientBuilder};
+    ///
+    /// let e = ClientBuilder::new(0, "3000,not an address", 0)
+    ///     .validate()
+    ///     .unwrap_err();
+    /// assert_eq!(
+    ///     e.as_slice(),
+    ///     [
+    ///         ConfigErrorKind::ConcurrencyMaxZero,
+    ///         ConfigErrorKind::AddressInvalid("not an address".into()),
+    ///     ]
+    /// );
+    /// ```
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut problems = Vec::new();
+        if self.concurrency_max == 0 {
+            problems.push(ConfigErrorKind::ConcurrencyMaxZero);
+        } else if self.concurrency_max > MAX_CONCURRENCY {
+            problems.push(ConfigErrorKind::ConcurrencyMaxTooLarge(
+                self.concurrency_max,
+            ));
+        }
+        if self.address.is_empty() {
+            problems.push(ConfigErrorKind::AddressEmpty);
+        } else {
+            problems.extend(
+                self.address
+                    .split(|&b| b == b',')
+                    .filter(|a| !is_valid_address(a))
+                    .map(|a| ConfigErrorKind::AddressInvalid(String::from_utf8_lossy(a).into())),
+            );
+        }
+        ConfigError::from_kinds(problems).map_or(Ok(()), Err)
+    }
+
+    /// Validate configuration and create a client.
+    pub fn build(self) -> Result<Client, BuildClientError> {
+        self.validate()?;
+        let mut client = Client::with_callbacks(
+            self.cluster_id,
+            self.address,
+            self.concurrency_max,
+            Callbacks::new(self.on_first_completion_thread, self.on_reply),
+        )?;
+        client.empty_batch_behavior = self.empty_batch_behavior;
+        Ok(client)
+    }
+}
+
+impl std::fmt::Debug for ClientBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientBuilder")
+            .field("cluster_id", &self.cluster_id)
+            .field("address", &self.address)
+            .field("concurrency_max", &self.concurrency_max)
+            .field("empty_batch_behavior", &self.empty_batch_behavior)
+            .field(
+                "on_first_completion_thread",
+                &self.on_first_completion_thread.is_some(),
+            )
+            .field("on_reply", &self.on_reply.is_some())
+            .finish()
+    }
+}
+
+/// Tigerbeetle accepts a port, an ip address or an ip address with a port.
+fn is_valid_address(address: &[u8]) -> bool {
+    let Ok(address) = std::str::from_utf8(address) else {
+        return false;
+    };
+    address.parse::<u16>().is_ok()
+        || address.parse::<IpAddr>().is_ok()
+        || address.parse::<SocketAddr>().is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problems(builder: ClientBuilder) -> Vec<ConfigErrorKind> {
+        builder.validate().unwrap_err().as_slice().to_vec()
+    }
+
+    #[test]
+    fn valid() {
+        ClientBuilder::new(0, "3000", 1).validate().unwrap();
+        ClientBuilder::new(0, "127.0.0.1:3000,3001", MAX_CONCURRENCY)
+            .validate()
+            .unwrap();
+    }
+
+    #[test]
+    fn concurrency_max_zero() {
+        assert_eq!(
+            problems(ClientBuilder::new(0, "3000", 0)),
+            [ConfigErrorKind::ConcurrencyMaxZero],
+        );
+    }
+
+    #[test]
+    fn concurrency_max_too_large() {
+        assert_eq!(
+            problems(ClientBuilder::new(0, "3000", MAX_CONCURRENCY + 1)),
+            [ConfigErrorKind::ConcurrencyMaxTooLarge(MAX_CONCURRENCY + 1)],
+        );
+    }
+
+    #[test]
+    fn address_empty() {
+        assert_eq!(
+            problems(ClientBuilder::new(0, "", 1)),
+            [ConfigErrorKind::AddressEmpty],
+        );
+    }
+
+    #[test]
+    fn address_invalid() {
+        assert_eq!(
+            problems(ClientBuilder::new(0, "3000,localhost:3001,", 1)),
+            [
+                ConfigErrorKind::AddressInvalid("localhost:3001".into()),
+                ConfigErrorKind::AddressInvalid("".into()),
+            ],
+        );
+    }
+}
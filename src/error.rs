@@ -0,0 +1,329 @@
+pub use core::error::*;
+
+/// Problems found in client configuration before creating a client.
+//
+// INVARIANT: self.0 must not be empty
+#[derive(Debug)]
+pub struct ConfigError(pub(crate) Vec<ConfigErrorKind>);
+
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigErrorKind {
+    /// `concurrency_max` is zero, so no request could ever be sent.
+    ConcurrencyMaxZero,
+    /// `concurrency_max` exceeds [`MAX_CONCURRENCY`].
+    ///
+    /// [`MAX_CONCURRENCY`]: crate::MAX_CONCURRENCY
+    ConcurrencyMaxTooLarge(u32),
+    /// Address string is empty.
+    AddressEmpty,
+    /// Address entry is neither a port, an ip address nor a socket address.
+    AddressInvalid(String),
+}
+
+/// No concurrency permit is available right now. Contains rejected input.
+pub struct WouldBlock<T>(pub T);
+
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum BuildClientError {
+    Config(ConfigError),
+    NewClient(NewClientError),
+}
+
+/// Failure of a request with a typed reply.
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum RequestError {
+    Send(SendError),
+    /// Reply couldn't be decoded into values of its operation.
+    MalformedReply(MalformedReply),
+    /// Request was aborted by [`Client::abort_pending`] before its reply
+    /// arrived.
+    ///
+    /// [`Client::abort_pending`]: crate::Client::abort_pending
+    Aborted,
+    /// No packet could be acquired, so nothing was sent.
+    Acquire(AcquirePacketError),
+}
+
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum GetAccountBalancesError {
+    Send(SendError),
+    /// Reply couldn't be decoded into balances.
+    MalformedReply(MalformedReply),
+    /// Request was aborted by [`Client::abort_pending`] before its reply
+    /// arrived.
+    ///
+    /// [`Client::abort_pending`]: crate::Client::abort_pending
+    Aborted,
+    /// No packet could be acquired, so nothing was sent.
+    Acquire(AcquirePacketError),
+    /// Account with this id doesn't exist.
+    AccountNotFound(u128),
+    /// Account with this id was created without
+    /// [`HISTORY`](crate::account::Flags::HISTORY) flag, so its balances are
+    /// not recorded.
+    BalancesNotRecorded(u128),
+    /// Requested limit is zero or above
+    /// [`Filter::MAX_LIMIT`](crate::account::Filter::MAX_LIMIT).
+    Limit(FilterLimitError),
+}
+
+/// Requests were still in flight when [`Client::shutdown_timeout`] expired.
+///
+/// [`Client::shutdown_timeout`]: crate::Client::shutdown_timeout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShutdownTimeout {
+    pub(crate) in_flight: usize,
+}
+
+impl ConfigError {
+    /// Get a slice of found problems. Never empty.
+    pub fn as_slice(&self) -> &[ConfigErrorKind] {
+        &self.0
+    }
+
+    /// Create error from a vec of problems.
+    ///
+    /// # Errors
+    ///
+    /// Returns `None` if `v.is_empty()`.
+    pub fn from_kinds(v: Vec<ConfigErrorKind>) -> Option<Self> {
+        (!v.is_empty()).then_some(ConfigError(v))
+    }
+}
+
+impl AsRef<[ConfigErrorKind]> for ConfigError {
+    fn as_ref(&self) -> &[ConfigErrorKind] {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid client configuration: ")?;
+        for (i, kind) in self.0.iter().enumerate() {
+            if i != 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{kind}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl std::fmt::Display for ConfigErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigErrorKind::ConcurrencyMaxZero => write!(f, "concurrency_max must not be zero"),
+            ConfigErrorKind::ConcurrencyMaxTooLarge(n) => write!(
+                f,
+                "concurrency_max {n} exceeds maximum of {}",
+                crate::MAX_CONCURRENCY
+            ),
+            ConfigErrorKind::AddressEmpty => write!(f, "address must not be empty"),
+            ConfigErrorKind::AddressInvalid(a) => write!(f, "invalid address `{a}`"),
+        }
+    }
+}
+
+impl std::error::Error for BuildClientError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(match self {
+            BuildClientError::Config(e) => e as _,
+            BuildClientError::NewClient(e) => e as _,
+        })
+    }
+}
+
+impl std::fmt::Display for BuildClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BuildClientError::Config(_) => "invalid client configuration",
+            BuildClientError::NewClient(_) => "error occured while creating a client",
+        }
+        .fmt(f)
+    }
+}
+
+impl From<ConfigError> for BuildClientError {
+    fn from(value: ConfigError) -> Self {
+        BuildClientError::Config(value)
+    }
+}
+
+impl From<NewClientError> for BuildClientError {
+    fn from(value: NewClientError) -> Self {
+        BuildClientError::NewClient(value)
+    }
+}
+
+impl<T> WouldBlock<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> std::fmt::Debug for WouldBlock<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WouldBlock").finish_non_exhaustive()
+    }
+}
+
+impl<T> std::fmt::Display for WouldBlock<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        "all concurrency permits are taken".fmt(f)
+    }
+}
+
+impl<T> std::error::Error for WouldBlock<T> {}
+
+impl std::error::Error for RequestError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(match self {
+            RequestError::Send(e) => e as _,
+            RequestError::MalformedReply(e) => e as _,
+            RequestError::Aborted => return None,
+            RequestError::Acquire(e) => e as _,
+        })
+    }
+}
+
+impl std::fmt::Display for RequestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RequestError::Send(_) => "error occured while sending packets".fmt(f),
+            RequestError::MalformedReply(e) => e.fmt(f),
+            RequestError::Aborted => "request was aborted before its reply arrived".fmt(f),
+            RequestError::Acquire(e) => e.fmt(f),
+        }
+    }
+}
+
+impl From<SendError> for RequestError {
+    fn from(value: SendError) -> Self {
+        RequestError::Send(value)
+    }
+}
+
+impl From<AcquirePacketError> for RequestError {
+    fn from(value: AcquirePacketError) -> Self {
+        RequestError::Acquire(value)
+    }
+}
+
+impl From<MalformedReply> for RequestError {
+    fn from(value: MalformedReply) -> Self {
+        RequestError::MalformedReply(value)
+    }
+}
+
+impl std::error::Error for GetAccountBalancesError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GetAccountBalancesError::Send(e) => Some(e),
+            GetAccountBalancesError::MalformedReply(e) => Some(e),
+            GetAccountBalancesError::Acquire(e) => Some(e),
+            GetAccountBalancesError::Limit(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for GetAccountBalancesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GetAccountBalancesError::Send(_) => {
+                write!(
+                    f,
+                    "error occured while sending packets for account balances"
+                )
+            }
+            GetAccountBalancesError::MalformedReply(e) => write!(f, "{e}"),
+            GetAccountBalancesError::Aborted => {
+                write!(f, "request was aborted before its reply arrived")
+            }
+            GetAccountBalancesError::Acquire(e) => write!(f, "{e}"),
+            GetAccountBalancesError::AccountNotFound(id) => write!(f, "account {id} not found"),
+            GetAccountBalancesError::BalancesNotRecorded(id) => write!(
+                f,
+                "balances of account {id} are not recorded, it lacks the history flag"
+            ),
+            GetAccountBalancesError::Limit(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<SendError> for GetAccountBalancesError {
+    fn from(value: SendError) -> Self {
+        GetAccountBalancesError::Send(value)
+    }
+}
+
+impl From<MalformedReply> for GetAccountBalancesError {
+    fn from(value: MalformedReply) -> Self {
+        GetAccountBalancesError::MalformedReply(value)
+    }
+}
+
+impl From<RequestError> for GetAccountBalancesError {
+    fn from(value: RequestError) -> Self {
+        match value {
+            RequestError::Send(e) => GetAccountBalancesError::Send(e),
+            RequestError::MalformedReply(e) => GetAccountBalancesError::MalformedReply(e),
+            RequestError::Aborted => GetAccountBalancesError::Aborted,
+            RequestError::Acquire(e) => GetAccountBalancesError::Acquire(e),
+        }
+    }
+}
+
+impl From<RequestError> for CreateAccountsError {
+    fn from(value: RequestError) -> Self {
+        match value {
+            RequestError::Send(e) => CreateAccountsError::Send(e),
+            RequestError::MalformedReply(e) => CreateAccountsError::MalformedReply(e),
+            RequestError::Aborted => CreateAccountsError::Aborted,
+            RequestError::Acquire(e) => CreateAccountsError::Acquire(e),
+        }
+    }
+}
+
+impl From<RequestError> for CreateTransfersError {
+    fn from(value: RequestError) -> Self {
+        match value {
+            RequestError::Send(e) => CreateTransfersError::Send(e),
+            RequestError::MalformedReply(e) => CreateTransfersError::MalformedReply(e),
+            RequestError::Aborted => CreateTransfersError::Aborted,
+            RequestError::Acquire(e) => CreateTransfersError::Acquire(e),
+        }
+    }
+}
+
+impl From<FilterLimitError> for GetAccountBalancesError {
+    fn from(value: FilterLimitError) -> Self {
+        GetAccountBalancesError::Limit(value)
+    }
+}
+
+impl ShutdownTimeout {
+    /// Number of requests in flight when the timeout expired.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight
+    }
+}
+
+impl std::fmt::Display for ShutdownTimeout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} requests still in flight after shutdown timeout",
+            self.in_flight
+        )
+    }
+}
+
+impl std::error::Error for ShutdownTimeout {}
@@ -1,34 +1,150 @@
+//! Safe high level async bindings to tigerbeetle client library.
+//!
+//! Client is runtime agnostic. It only depends on `tokio` for its `sync`
+//! primitives, oneshot channel and semaphore, which don't need the tokio
+//! runtime and work with any executor, e.g. `async-std` or `smol`. Replies
+//! are delivered from the tigerbeetle thread, so no reactor is involved.
 #![forbid(unsafe_code)]
 
+mod batcher;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+mod builder;
+pub mod error;
+mod pages;
 mod reply;
+mod retry;
 
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    fmt::Write as _,
+    future::Future,
+    mem,
+    net::SocketAddr,
+    panic::{self, AssertUnwindSafe},
+    sync::{Arc, Mutex, MutexGuard, Once, PoisonError},
+    time::{Duration, Instant, SystemTime},
+};
 
-use error::{NewClientError, NewClientErrorKind};
+use error::{
+    GetAccountBalancesError, NewClientError, NewClientErrorKind, RequestError, WouldBlock,
+};
 use reply::Reply;
 use tokio::sync::{oneshot, OwnedSemaphorePermit, Semaphore};
 
 use core::{
-    error::{CreateAccountsError, CreateTransfersError, SendError},
+    error::{
+        BatchTooLarge, CreateAccountsApiError, CreateAccountsError,
+        CreateAccountsIndividualApiError, CreateTransferErrorKind, CreateTransfersApiError,
+        CreateTransfersError, MalformedReply, SendError, SendErrorKind,
+    },
     util::{RawConstPtr, SendAsBytesOwnedSlice, SendOwnedSlice},
 };
 
-pub use core::{self, account, error, transfer, Account, Transfer};
+pub use batcher::TransferBatcher;
+pub use builder::{ClientBuilder, EmptyBatchBehavior};
+pub use core::{
+    self, account, client_version, decode, max_batch_len, transfer, Account, Operation,
+    OperationKind, Transfer, MAX_BATCH_ACCOUNTS, MAX_BATCH_TRANSFERS, MESSAGE_BODY_SIZE_MAX,
+};
+pub use pages::AccountBalancesPages;
+pub use reply::{ReplyMeta, SharedReply};
+pub use retry::RetryPolicy;
+
+/// Highest concurrency the client can honour, the size limit of the packet
+/// pool `tb_client_init` fails with [`NewClientErrorKind::ConcurrencyMaxInvalid`]
+/// above. Requested `concurrency_max` values above this are clamped by
+/// [`Client::new`], see [`Client::effective_concurrency`], and rejected by
+/// [`ClientBuilder::validate`].
+///
+/// Mirrors the `concurrency_max` limit checked by `tb_client_init` in
+/// tigerbeetle 0.15.3 (`src/clients/c/tb_client/context.zig`).
+pub const MAX_CONCURRENCY: u32 = 8192;
 
+/// High level async tigerbeetle client.
+///
+/// Replies are received on a single thread spawned by `tb_client`, see
+/// [`ClientBuilder::on_first_completion_thread`] to identify it.
+///
+/// To stop waiting for in-flight requests, drop their futures, or call
+/// [`Self::abort_pending`] to fail all of them at once. Request data and its
+/// concurrency permit are owned by the packet, not by the future, so they
+/// stay alive until tigerbeetle completes the request and are released on
+/// the completion thread, with the reply discarded. Dropping the client
+/// afterwards blocks until every such request is completed.
 pub struct Client {
-    inner: core::Client<&'static Callbacks>,
+    inner: core::Client<Box<Callbacks>>,
+    /// Has exactly as many permits as there are packets in the pool allocated
+    /// by `tb_client_init`, so acquiring a packet with a permit never fails.
     sema: Arc<Semaphore>,
+    pending: Arc<Mutex<PendingReplies>>,
+    concurrency_max: u32,
+    effective_concurrency: u32,
+    empty_batch_behavior: EmptyBatchBehavior,
+}
+
+/// Per-account result of [`Client::create_accounts_detailed`].
+#[derive(Debug)]
+pub struct CreateAccountsOutcome {
+    succeeded: usize,
+    errors: Vec<CreateAccountsIndividualApiError>,
+}
+
+struct Callbacks {
+    first_completion: Once,
+    on_first_completion_thread: Option<Arc<dyn Fn() + Send + Sync>>,
+    on_reply: Option<Arc<dyn Fn(ReplyMeta) + Send + Sync>>,
 }
 
-struct Callbacks;
+type ReplySender = oneshot::Sender<Result<Reply, SendError>>;
+
+/// Reply senders of in-flight requests. Kept by the client rather than by
+/// packets, so that [`Client::abort_pending`] can drop them without waiting
+/// for completions.
+#[derive(Default)]
+struct PendingReplies {
+    next_key: u64,
+    senders: HashMap<u64, ReplySender>,
+}
+
+/// Entry of a request in [`PendingReplies`], owned by its packet. Removes the
+/// sender when dropped, so a request dropped without a reply resolves as
+/// [`RequestError::Aborted`] instead of staying in the map.
+struct PendingReply {
+    key: u64,
+    pending: Arc<Mutex<PendingReplies>>,
+}
 
 struct UserData {
-    reply_sender: oneshot::Sender<Result<Reply, SendError>>,
+    reply: PendingReply,
     _permit: OwnedSemaphorePermit,
     data: SendAsBytesOwnedSlice,
+    /// Skip decoding the reply, see [`Client::submit_raw`].
+    raw_reply: bool,
 }
 
 impl Client {
+    /// Create a new client.
+    ///
+    /// `concurrency_max` is clamped to [`MAX_CONCURRENCY`]. Use
+    /// [`Self::effective_concurrency`] to get the value actually in use, or
+    /// [`ClientBuilder`] to get [`ConfigErrorKind::ConcurrencyMaxTooLarge`]
+    /// instead of clamping. Clamped value is both the size of the packet
+    /// pool allocated by `tb_client_init` and the number of permits of the
+    /// semaphore limiting in-flight requests.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NewClientErrorKind::ConcurrencyMaxInvalid`] if
+    /// `concurrency_max` is zero, so no request could ever acquire a permit.
+    ///
+    /// [`NewClientErrorKind::AddressInvalid`] doesn't tell which of the
+    /// comma-separated addresses is malformed. Use [`ClientBuilder`], which
+    /// checks every address before calling `tb_client_init` and names the
+    /// offending one in [`ConfigErrorKind::AddressInvalid`].
+    ///
+    /// [`ConfigErrorKind::AddressInvalid`]: error::ConfigErrorKind::AddressInvalid
+    /// [`ConfigErrorKind::ConcurrencyMaxTooLarge`]: error::ConfigErrorKind::ConcurrencyMaxTooLarge
     pub fn new<A>(
         cluster_id: u128,
         address: A,
@@ -37,80 +153,477 @@ impl Client {
     where
         A: AsRef<[u8]>,
     {
+        Self::with_callbacks(
+            cluster_id,
+            address,
+            concurrency_max,
+            Callbacks::new(None, None),
+        )
+    }
+
+    /// Same as [`Self::new`], but takes replica addresses as socket addresses
+    /// instead of a comma-separated string.
+    pub fn from_socket_addrs(
+        cluster_id: u128,
+        addresses: &[SocketAddr],
+        concurrency_max: u32,
+    ) -> Result<Self, NewClientError> {
+        Self::new(cluster_id, join_addresses(addresses), concurrency_max)
+    }
+
+    fn with_callbacks<A>(
+        cluster_id: u128,
+        address: A,
+        concurrency_max: u32,
+        callbacks: Callbacks,
+    ) -> Result<Self, NewClientError>
+    where
+        A: AsRef<[u8]>,
+    {
+        if concurrency_max == 0 {
+            return Err(NewClientErrorKind::ConcurrencyMaxInvalid.into());
+        }
+        let effective_concurrency = concurrency_max.min(MAX_CONCURRENCY);
         Ok(Client {
             sema: Arc::new(Semaphore::new(
-                concurrency_max
+                effective_concurrency
                     .try_into()
                     .map_err(|_| NewClientErrorKind::ConcurrencyMaxInvalid)?,
             )),
-            inner: core::Client::with_callback(cluster_id, address, concurrency_max, &Callbacks)?,
+            inner: core::Client::with_callback(
+                cluster_id,
+                address,
+                effective_concurrency,
+                Box::new(callbacks),
+            )?,
+            concurrency_max,
+            effective_concurrency,
+            pending: Arc::default(),
+            empty_batch_behavior: EmptyBatchBehavior::default(),
         })
     }
 
+    /// Cluster id the client was created with.
+    pub fn cluster_id(&self) -> u128 {
+        self.inner.cluster_id()
+    }
+
+    /// Maximum number of concurrent requests as requested at creation.
+    pub fn concurrency_max(&self) -> u32 {
+        self.concurrency_max
+    }
+
+    /// Maximum number of concurrent requests after clamping `concurrency_max`
+    /// to [`MAX_CONCURRENCY`].
+    pub fn effective_concurrency(&self) -> u32 {
+        self.effective_concurrency
+    }
+
+    /// Number of requests that could be submitted right now without waiting
+    /// for others to complete.
+    pub fn available_concurrency(&self) -> usize {
+        self.sema.available_permits()
+    }
+
+    /// Wait up to `timeout` for in-flight requests to complete and close the
+    /// client.
+    ///
+    /// Blocks the current thread, polling [`Self::available_concurrency`].
+    /// Requests whose futures were dropped are still in flight until
+    /// tigerbeetle replies to them.
+    ///
+    /// Must not be called from async code: blocking an executor thread can
+    /// keep the very requests it waits for from completing. Call it from a
+    /// blocking context instead, e.g. `tokio::task::spawn_blocking`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ShutdownTimeout`] if some requests are still in flight after
+    /// `timeout`. Client is closed anyway, blocking in `tb_client_deinit`
+    /// which is required to free its resources. Abandoned requests still have
+    /// their user data dropped, releasing submitted data.
+    ///
+    /// [`ShutdownTimeout`]: error::ShutdownTimeout
+    pub fn shutdown_timeout(self, timeout: Duration) -> Result<(), error::ShutdownTimeout> {
+        const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+        let deadline = Instant::now() + timeout;
+        let total = self.effective_concurrency as usize;
+        loop {
+            let in_flight = total - self.sema.available_permits();
+            if in_flight == 0 {
+                return Ok(());
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(error::ShutdownTimeout { in_flight });
+            }
+            std::thread::sleep(POLL_INTERVAL.min(deadline - now));
+        }
+    }
+
+    /// Stop waiting for every in-flight request, failing their futures with
+    /// [`RequestError::Aborted`] right away, e.g. to shut down on an error.
+    ///
+    /// Only reply senders are dropped here. Packets stay with tigerbeetle,
+    /// which still owns request data and concurrency permits, so
+    /// [`Self::available_concurrency`] recovers only as requests complete.
+    /// Their replies are discarded on the completion thread. Dropping the
+    /// client then blocks in `tb_client_deinit` until every packet is
+    /// completed or dropped, releasing the rest. Requests submitted after
+    /// this call aren't affected.
+    pub fn abort_pending(&self) {
+        let senders = mem::take(&mut PendingReplies::lock(&self.pending).senders);
+        // Wake receivers outside of the lock
+        drop(senders);
+    }
+
     pub async fn create_accounts<T>(&self, accounts: T) -> Result<(), CreateAccountsError>
     where
         T: Into<SendOwnedSlice<Account>>,
     {
         let accounts: SendOwnedSlice<Account> = accounts.into();
         if accounts.is_empty() {
-            return Ok(());
+            return Ok(self.empty_batch()?);
         }
+        let len = accounts.len();
         Ok(self
             .submit(
                 accounts.into_as_bytes(),
                 core::OperationKind::CreateAccounts.into(),
             )
+            .await
+            .map_err(|e| batch_request_error::<Account, CreateAccountsError>(len, e))?
+            .into_create_accounts()??)
+    }
+
+    /// Same as [`Self::create_accounts`], but takes any domain values
+    /// convertible into accounts, collecting them into a single batch.
+    pub async fn create_accounts_from<I>(&self, accounts: I) -> Result<(), CreateAccountsError>
+    where
+        I: IntoIterator,
+        I::Item: account::IntoAccount,
+    {
+        let accounts: SendOwnedSlice<Account> = accounts
+            .into_iter()
+            .map(account::IntoAccount::into_account)
+            .collect();
+        self.create_accounts(accounts).await
+    }
+
+    /// Same as [`Self::create_accounts`], but reports how many accounts were
+    /// created alongside the failed ones.
+    ///
+    /// Tigerbeetle applies every account of a batch independently, unless it
+    /// is part of a chain of [`account::Flags::LINKED`] accounts, which
+    /// succeed or fail together. So an api error doesn't mean the whole batch
+    /// was rejected.
+    pub async fn create_accounts_detailed<T>(
+        &self,
+        accounts: T,
+    ) -> Result<CreateAccountsOutcome, RequestError>
+    where
+        T: Into<SendOwnedSlice<Account>>,
+    {
+        let accounts: SendOwnedSlice<Account> = accounts.into();
+        let total = accounts.len();
+        let errors = if accounts.is_empty() {
+            self.empty_batch()?;
+            Vec::new()
+        } else {
+            self.submit(
+                accounts.into_as_bytes(),
+                core::OperationKind::CreateAccounts.into(),
+            )
             .await?
-            .into_create_accounts()?)
+            .into_create_accounts()?
+            .map_or_else(CreateAccountsApiError::into_vec, |()| Vec::new())
+        };
+        Ok(CreateAccountsOutcome {
+            succeeded: total - errors.len(),
+            errors,
+        })
     }
 
+    /// # Examples
+    ///
+    /// Batch can be anything convertible into [`SendOwnedSlice`], e.g. a
+    /// `Vec`, an `Arc<[Transfer]>` or an array:
+    ///
+    /// ```no_run
+    /// use tigerbeetle_unofficial::{Client, Transfer};
+    ///
+    /// # pollster::block_on(async {
+    /// let client = Client::new(0, "3000", 32).unwrap();
+    /// let t1 = Transfer::new(1)
+    ///     .with_debit_account_id(1)
+    ///     .with_credit_account_id(2)
+    ///     .with_amount(10)
+    ///     .with_ledger(1)
+    ///     .with_code(1);
+    /// let t2 = t1.with_id(2);
+    /// client.create_transfers([t1, t2]).await.unwrap();
+    /// # });
+    /// ```
     pub async fn create_transfers<T>(&self, transfers: T) -> Result<(), CreateTransfersError>
     where
         T: Into<SendOwnedSlice<Transfer>>,
     {
         let transfers: SendOwnedSlice<Transfer> = transfers.into();
         if transfers.is_empty() {
-            return Ok(());
+            return Ok(self.empty_batch()?);
         }
+        let len = transfers.len();
         Ok(self
             .submit(
                 transfers.into_as_bytes(),
                 core::OperationKind::CreateTransfers.into(),
             )
+            .await
+            .map_err(|e| batch_request_error::<Transfer, CreateTransfersError>(len, e))?
+            .into_create_transfers()??)
+    }
+
+    /// Same as [`Self::create_transfers`], but takes any domain values
+    /// convertible into transfers, collecting them into a single batch.
+    pub async fn create_transfers_from<I>(&self, transfers: I) -> Result<(), CreateTransfersError>
+    where
+        I: IntoIterator,
+        I::Item: transfer::IntoTransfer,
+    {
+        let transfers: SendOwnedSlice<Transfer> = transfers
+            .into_iter()
+            .map(transfer::IntoTransfer::into_transfer)
+            .collect();
+        self.create_transfers(transfers).await
+    }
+
+    /// Create transfers, returning submitted batch alongside the result for
+    /// correlation in later stages.
+    ///
+    /// Batch is retained by shared ownership and isn't copied, so passing a
+    /// `Vec<Transfer>` costs one copy into the [`Arc`] while an existing
+    /// `Arc<[Transfer]>` is just cloned.
+    pub async fn create_transfers_echo<T>(
+        &self,
+        transfers: T,
+    ) -> Result<(Arc<[Transfer]>, Result<(), CreateTransfersApiError>), RequestError>
+    where
+        T: Into<Arc<[Transfer]>>,
+    {
+        let transfers: Arc<[Transfer]> = transfers.into();
+        if transfers.is_empty() {
+            self.empty_batch()?;
+            return Ok((transfers, Ok(())));
+        }
+        let data = SendOwnedSlice::from(transfers.clone()).into_as_bytes();
+        let res = self
+            .submit(data, core::OperationKind::CreateTransfers.into())
             .await?
-            .into_create_transfers()?)
+            .into_create_transfers()?;
+        Ok((transfers, res))
+    }
+
+    /// Check transfers like [`transfer::validate_batch`] without sending
+    /// anything, e.g. to validate input offline.
+    ///
+    /// # Errors
+    ///
+    /// Returns every found problem along with the index of its transfer.
+    pub fn create_transfers_dry_run(
+        &self,
+        transfers: &[Transfer],
+    ) -> Result<(), Vec<(usize, CreateTransferErrorKind)>> {
+        transfer::validate_batch(transfers)
+    }
+
+    /// Non-blocking version of [`Self::create_transfers`].
+    ///
+    /// Takes a concurrency permit right away, then returned future submits
+    /// transfers.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WouldBlock`] with untouched `transfers` if all permits are
+    /// taken by other requests.
+    pub fn try_create_transfers<T>(
+        &self,
+        transfers: T,
+    ) -> Result<impl Future<Output = Result<(), CreateTransfersError>> + Send + '_, WouldBlock<T>>
+    where
+        T: Into<SendOwnedSlice<Transfer>>,
+    {
+        let Ok(permit) = self.sema.clone().try_acquire_owned() else {
+            return Err(WouldBlock(transfers));
+        };
+        let transfers: SendOwnedSlice<Transfer> = transfers.into();
+        Ok(async move {
+            if transfers.is_empty() {
+                return Ok(self.empty_batch()?);
+            }
+            let len = transfers.len();
+            Ok(self
+                .submit_with_permit(
+                    transfers.into_as_bytes(),
+                    core::OperationKind::CreateTransfers.into(),
+                    permit,
+                )
+                .await
+                .map_err(|e| batch_request_error::<Transfer, CreateTransfersError>(len, e))?
+                .into_create_transfers()??)
+        })
+    }
+
+    /// Create transfers, retrying on send errors selected by `policy`.
+    ///
+    /// Api errors and [`CreateTransfersError::BatchTooLarge`] are returned
+    /// right away. `sleep` is used to wait between attempts, e.g.
+    /// `tokio::time::sleep`, so that the client stays runtime agnostic.
+    ///
+    /// Bundled `tb_client` reports no transient send errors, see
+    /// [`SendError::is_retriable`], and retries lost messages internally. So
+    /// this only retries kinds explicitly listed in `policy`, and with an
+    /// empty policy it's the same as [`Self::create_transfers`].
+    ///
+    /// Retrying doesn't duplicate already applied transfers only because
+    /// tigerbeetle rejects a transfer with an already existing id. Keep ids
+    /// stable between attempts, which is guaranteed here as the same batch is
+    /// resubmitted.
+    pub async fn create_transfers_retry<T, S, F>(
+        &self,
+        transfers: T,
+        policy: &RetryPolicy,
+        mut sleep: S,
+    ) -> Result<(), CreateTransfersError>
+    where
+        T: Into<Arc<[Transfer]>>,
+        S: FnMut(Duration) -> F,
+        F: Future<Output = ()>,
+    {
+        let transfers: Arc<[Transfer]> = transfers.into();
+        let start = Instant::now();
+        let mut attempt = 1;
+        loop {
+            match self.create_transfers(transfers.clone()).await {
+                Err(CreateTransfersError::Send(e))
+                    if attempt < policy.max_attempts() && policy.should_retry(e) =>
+                {
+                    let delay = policy.backoff(attempt);
+                    if start.elapsed() + delay > policy.max_elapsed() {
+                        return Err(e.into());
+                    }
+                    sleep(delay).await;
+                    attempt += 1;
+                }
+                res => return res,
+            }
+        }
     }
 
     pub async fn get_account_balances<T>(
         &self,
         filter: T,
-    ) -> Result<Vec<account::Balance>, SendError>
+    ) -> Result<Vec<account::Balance>, RequestError>
     where
         T: RawConstPtr<Target = account::Filter> + Send + 'static,
     {
         let filter: SendOwnedSlice<account::Filter> = SendOwnedSlice::from_single(filter);
-        self.submit(
-            filter.into_as_bytes(),
-            core::OperationKind::GetAccountBalances.into(),
-        )
-        .await
-        .map(Reply::into_get_account_balances)
+        Ok(self
+            .submit(
+                filter.into_as_bytes(),
+                core::OperationKind::GetAccountBalances.into(),
+            )
+            .await?
+            .into_get_account_balances()?)
+    }
+
+    /// Same as [`Self::get_account_balances`], but first checks that the
+    /// account exists and records its balances.
+    ///
+    /// Otherwise tigerbeetle just returns no balances. Costs an extra account
+    /// lookup.
+    pub async fn get_account_balances_checked(
+        &self,
+        filter: account::Filter,
+    ) -> Result<Vec<account::Balance>, GetAccountBalancesError> {
+        let account_id = filter.account_id();
+        let Some(account) = self.lookup_account(account_id).await? else {
+            return Err(GetAccountBalancesError::AccountNotFound(account_id));
+        };
+        if !account.flags().contains(account::Flags::HISTORY) {
+            return Err(GetAccountBalancesError::BalancesNotRecorded(account_id));
+        }
+        Ok(self.get_account_balances(Box::new(filter)).await?)
+    }
+
+    /// Balance history of the account, requested in pages of
+    /// [`account::Filter::MAX_LIMIT`] balances as it's consumed.
+    ///
+    /// Account must have [`account::Flags::HISTORY`] set for tigerbeetle to
+    /// keep balance snapshots, otherwise history is empty.
+    pub fn account_balances_stream(&self, account_id: u128) -> AccountBalancesPages<'_> {
+        AccountBalancesPages::new(self, account_id)
     }
 
-    pub async fn get_account_transfers<T>(&self, filter: T) -> Result<Vec<Transfer>, SendError>
+    /// Net posted balance history of the account as `(timestamp, net)` pairs,
+    /// see [`account::Balance::net_posted`].
+    ///
+    /// Account must have [`account::Flags::HISTORY`] set for tigerbeetle to
+    /// keep balance snapshots.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GetAccountBalancesError::Limit`] if `limit` is zero or above
+    /// [`account::Filter::MAX_LIMIT`], without sending anything.
+    pub async fn get_account_net_balances(
+        &self,
+        account_id: u128,
+        limit: u32,
+    ) -> Result<Vec<(SystemTime, i128)>, GetAccountBalancesError> {
+        let filter = account::Filter::new(account_id, 1)
+            .try_with_limit(limit)?
+            .with_flags(account::FilterFlags::DEBITS | account::FilterFlags::CREDITS);
+        let balances = self.get_account_balances(Box::new(filter)).await?;
+        Ok(balances
+            .iter()
+            .map(|b| (b.timestamp(), b.net_posted()))
+            .collect())
+    }
+
+    pub async fn get_account_transfers<T>(&self, filter: T) -> Result<Vec<Transfer>, RequestError>
     where
         T: RawConstPtr<Target = account::Filter> + Send + 'static,
     {
         let filter: SendOwnedSlice<account::Filter> = SendOwnedSlice::from_single(filter);
-        self.submit(
-            filter.into_as_bytes(),
-            core::OperationKind::GetAccountTransfers.into(),
-        )
-        .await
-        .map(Reply::into_get_account_transfers)
+        Ok(self
+            .submit(
+                filter.into_as_bytes(),
+                core::OperationKind::GetAccountTransfers.into(),
+            )
+            .await?
+            .into_get_account_transfers()?)
     }
 
-    pub async fn lookup_accounts<T>(&self, ids: T) -> Result<Vec<Account>, SendError>
+    /// Look up a single account, returning `None` if it doesn't exist.
+    pub async fn lookup_account(&self, id: u128) -> Result<Option<Account>, RequestError> {
+        Ok(self.lookup_accounts(vec![id]).await?.pop())
+    }
+
+    /// Look up accounts by ids, skipping missing ones.
+    ///
+    /// If none of the accounts exist, returned vector is empty and not allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tigerbeetle_unofficial::Client;
+    ///
+    /// # pollster::block_on(async {
+    /// let client = Client::new(0, "3000", 32).unwrap();
+    /// let accounts = client.lookup_accounts([1, 2]).await.unwrap();
+    /// # });
+    /// ```
+    pub async fn lookup_accounts<T>(&self, ids: T) -> Result<Vec<Account>, RequestError>
     where
         T: Into<SendOwnedSlice<u128>>,
     {
@@ -118,15 +631,24 @@ impl Client {
         if ids.is_empty() {
             return Ok(Vec::new());
         }
-        self.submit(
-            ids.into_as_bytes(),
-            core::OperationKind::LookupAccounts.into(),
-        )
-        .await
-        .map(Reply::into_lookup_accounts)
+        Ok(self
+            .submit(
+                ids.into_as_bytes(),
+                core::OperationKind::LookupAccounts.into(),
+            )
+            .await?
+            .into_lookup_accounts()?)
     }
 
-    pub async fn lookup_transfers<T>(&self, ids: T) -> Result<Vec<Transfer>, SendError>
+    /// Look up a single transfer, returning `None` if it doesn't exist.
+    pub async fn lookup_transfer(&self, id: u128) -> Result<Option<Transfer>, RequestError> {
+        Ok(self.lookup_transfers(vec![id]).await?.pop())
+    }
+
+    /// Look up transfers by ids, skipping missing ones.
+    ///
+    /// If none of the transfers exist, returned vector is empty and not allocated.
+    pub async fn lookup_transfers<T>(&self, ids: T) -> Result<Vec<Transfer>, RequestError>
     where
         T: Into<SendOwnedSlice<u128>>,
     {
@@ -134,29 +656,181 @@ impl Client {
         if ids.is_empty() {
             return Ok(Vec::new());
         }
-        self.submit(
-            ids.into_as_bytes(),
-            core::OperationKind::LookupTransfers.into(),
-        )
-        .await
-        .map(Reply::into_lookup_transfers)
+        Ok(self
+            .submit(
+                ids.into_as_bytes(),
+                core::OperationKind::LookupTransfers.into(),
+            )
+            .await?
+            .into_lookup_transfers()?)
+    }
+
+    /// Look up transfers by ids and call `f` on every found transfer, in the
+    /// order of the server reply.
+    ///
+    /// Reply payload is only valid inside of the completion callback on the
+    /// tigerbeetle thread, so it's still copied out once as raw bytes. But
+    /// transfers are read out of it one by one, without allocating a vector
+    /// of them.
+    pub async fn lookup_transfers_for_each<T, F>(&self, ids: T, f: F) -> Result<(), RequestError>
+    where
+        T: Into<SendOwnedSlice<u128>>,
+        F: FnMut(Transfer),
+    {
+        let ids: SendOwnedSlice<u128> = ids.into();
+        if ids.is_empty() {
+            return Ok(());
+        }
+        let operation = core::OperationKind::LookupTransfers.into();
+        let permit = self.sema.clone().acquire_owned().await.unwrap();
+        let payload = self
+            .submit_packet(ids.into_as_bytes(), operation, permit, true)
+            .await?
+            .into_raw();
+        core::decode::lookup_transfers_iter(&payload)
+            .map_err(|e| MalformedReply::from_decode_error(operation, e))?
+            .for_each(f);
+        Ok(())
+    }
+
+    /// Make a round trip to the cluster and measure its latency, e.g. for a
+    /// readiness probe.
+    ///
+    /// Tigerbeetle has no ping request and `tb_client` doesn't report
+    /// whether it's registered with the cluster, so this looks up the account
+    /// with id `0`, which can never exist. Latency includes waiting for a
+    /// free concurrency permit.
+    ///
+    /// `tb_client` retries requests until the cluster replies, so an
+    /// unreachable cluster makes this wait forever instead of failing. Race
+    /// it against a timer of your runtime to bound the wait.
+    pub async fn ping(&self) -> Result<Duration, RequestError> {
+        let start = Instant::now();
+        self.lookup_accounts(vec![0]).await?;
+        Ok(start.elapsed())
+    }
+
+    /// Same as [`Self::lookup_transfers`], but returns reply which can be
+    /// cheaply cloned to hand out to many subscribers.
+    pub async fn lookup_transfers_shared<T>(
+        &self,
+        ids: T,
+    ) -> Result<SharedReply<Transfer>, RequestError>
+    where
+        T: Into<SendOwnedSlice<u128>>,
+    {
+        self.lookup_transfers(ids).await.map(SharedReply::from)
+    }
+
+    /// Submit `operation` with raw `payload` and get raw reply bytes back.
+    ///
+    /// Allows using operations of newer tigerbeetle releases before typed
+    /// methods for them are added. Reply is never decoded, even for
+    /// operations this client knows.
+    pub async fn submit_raw<T>(
+        &self,
+        operation: core::Operation,
+        payload: T,
+    ) -> Result<Vec<u8>, RequestError>
+    where
+        T: Into<SendOwnedSlice<u8>>,
+    {
+        let payload: SendOwnedSlice<u8> = payload.into();
+        let permit = self.sema.clone().acquire_owned().await.unwrap();
+        self.submit_packet(payload.into_as_bytes(), operation, permit, true)
+            .await
+            .map(Reply::into_raw)
+    }
+
+    /// Result of a create request with an empty batch according to
+    /// [`EmptyBatchBehavior`].
+    fn empty_batch(&self) -> Result<(), SendError> {
+        match self.empty_batch_behavior {
+            EmptyBatchBehavior::NoOp => Ok(()),
+            EmptyBatchBehavior::Error => Err(SendErrorKind::InvalidDataSize.into()),
+        }
     }
 
     async fn submit(
         &self,
         data: SendAsBytesOwnedSlice,
         operation: core::Operation,
-    ) -> Result<Reply, SendError> {
+    ) -> Result<Reply, RequestError> {
         let permit = self.sema.clone().acquire_owned().await.unwrap();
+        self.submit_with_permit(data, operation, permit).await
+    }
+
+    async fn submit_with_permit(
+        &self,
+        data: SendAsBytesOwnedSlice,
+        operation: core::Operation,
+        permit: OwnedSemaphorePermit,
+    ) -> Result<Reply, RequestError> {
+        self.submit_packet(data, operation, permit, false).await
+    }
+
+    /// Submit a packet, keeping reply as [`Reply::Raw`] if `raw_reply` is
+    /// set.
+    async fn submit_packet(
+        &self,
+        data: SendAsBytesOwnedSlice,
+        operation: core::Operation,
+        permit: OwnedSemaphorePermit,
+        raw_reply: bool,
+    ) -> Result<Reply, RequestError> {
         let (reply_sender, reply_receiver) = oneshot::channel();
         let user_data = Box::new(UserData {
-            reply_sender,
+            reply: PendingReply::new(&self.pending, reply_sender),
             _permit: permit,
             data,
+            raw_reply,
         });
-        let packet = self.inner.acquire(user_data, operation).unwrap();
+        // Shouldn't fail: semaphore permits never exceed the packet pool, and
+        // client can't be shut down while borrowed, since `Drop` needs
+        // exclusive access and blocks in `tb_client_deinit`. User data is
+        // dropped on failure, releasing the permit and the reply sender.
+        let packet = self.inner.acquire(user_data, operation)?;
         packet.submit();
-        reply_receiver.await.unwrap()
+        // Sender is dropped without a reply if the request was aborted or its
+        // packet was dropped without being completed
+        match reply_receiver.await {
+            Ok(reply) => Ok(reply?),
+            Err(_) => Err(RequestError::Aborted),
+        }
+    }
+}
+
+impl CreateAccountsOutcome {
+    /// Number of created accounts.
+    pub fn succeeded(&self) -> usize {
+        self.succeeded
+    }
+
+    /// Errors of accounts which weren't created, ordered by index.
+    pub fn errors(&self) -> &[CreateAccountsIndividualApiError] {
+        &self.errors
+    }
+
+    pub fn into_errors(self) -> Vec<CreateAccountsIndividualApiError> {
+        self.errors
+    }
+
+    /// Whether every account was created.
+    pub fn is_success(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+impl Callbacks {
+    fn new(
+        on_first_completion_thread: Option<Arc<dyn Fn() + Send + Sync>>,
+        on_reply: Option<Arc<dyn Fn(ReplyMeta) + Send + Sync>>,
+    ) -> Self {
+        Callbacks {
+            first_completion: Once::new(),
+            on_first_completion_thread,
+            on_reply,
+        }
     }
 }
 
@@ -164,13 +838,69 @@ impl core::Callbacks for Callbacks {
     type UserDataPtr = Box<UserData>;
 
     fn on_completion(&self, packet: core::Packet<'_, Self::UserDataPtr>, payload: &[u8]) {
+        if let Some(hook) = &self.on_first_completion_thread {
+            self.first_completion.call_once(|| call_hook(&**hook));
+        }
         let status = packet.status();
         let operation = packet.operation();
+        if let Some(on_reply) = &self.on_reply {
+            let meta = ReplyMeta {
+                operation,
+                received_at: Instant::now(),
+                status,
+            };
+            call_hook(|| on_reply(meta));
+        }
         let user_data = packet.into_user_data();
-        user_data
-            .reply_sender
-            .send(status.map(|()| Reply::copy_from_reply(operation.kind(), payload)))
-            .unwrap_or_else(|_| panic!("Unexpected: reply receiver is already dropped"));
+        let reply = status.map(|()| {
+            if user_data.raw_reply {
+                Reply::Raw {
+                    operation: operation.code(),
+                    payload: payload.to_vec(),
+                }
+            } else {
+                Reply::copy_from_reply(operation, payload)
+            }
+        });
+        // Sender is gone if the request was aborted and receiver is gone if
+        // the request future was dropped, the reply is just discarded then
+        if let Some(sender) = user_data.reply.take() {
+            let _ = sender.send(reply);
+        }
+    }
+}
+
+impl PendingReplies {
+    /// Lock ignoring poisoning, map is never left inconsistent.
+    fn lock(this: &Mutex<Self>) -> MutexGuard<'_, Self> {
+        this.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl PendingReply {
+    fn new(pending: &Arc<Mutex<PendingReplies>>, sender: ReplySender) -> Self {
+        let mut guard = PendingReplies::lock(pending);
+        let key = guard.next_key;
+        guard.next_key += 1;
+        guard.senders.insert(key, sender);
+        PendingReply {
+            key,
+            pending: pending.clone(),
+        }
+    }
+
+    /// Take the sender unless the request was aborted.
+    fn take(&self) -> Option<ReplySender> {
+        PendingReplies::lock(&self.pending)
+            .senders
+            .remove(&self.key)
+    }
+}
+
+impl Drop for PendingReply {
+    fn drop(&mut self) {
+        // Wake receiver outside of the lock
+        let _sender = self.take();
     }
 }
 
@@ -180,6 +910,42 @@ impl core::UserData for UserData {
     }
 }
 
+/// Run user hook on the completion thread, containing its panic.
+///
+/// Panic unwinding out of a hook would skip the reply, dropping its sender,
+/// and unwind into `tb_client`. Panic of the first completion hook would
+/// also poison [`Callbacks::first_completion`], failing every later reply.
+/// Default panic hook has already reported the panic by the time it's
+/// caught.
+fn call_hook(hook: impl FnOnce()) {
+    let _ = panic::catch_unwind(AssertUnwindSafe(hook));
+}
+
+/// Format addresses as `"ip1:port1,ip2:port2"` expected by `tb_client_init`.
+fn join_addresses(addresses: &[SocketAddr]) -> String {
+    let mut out = String::new();
+    for (i, address) in addresses.iter().enumerate() {
+        if i != 0 {
+            out.push(',');
+        }
+        write!(out, "{address}").unwrap();
+    }
+    out
+}
+
+/// Report failure of a request with a batch of `len` values of `T`, telling
+/// apart batches too large for a single request.
+fn batch_request_error<T, E>(len: usize, e: RequestError) -> E
+where
+    E: From<RequestError> + From<BatchTooLarge>,
+{
+    match e {
+        RequestError::Send(e) => BatchTooLarge::from_send_error::<T>(len, e)
+            .map_or_else(|| RequestError::Send(e).into(), E::from),
+        e => e.into(),
+    }
+}
+
 fn _test_thread_safe(
     client: Client,
     accounts: Vec<Account>,
@@ -199,3 +965,207 @@ fn _test_thread_safe(
     {
     }
 }
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn join_addresses() {
+        assert_eq!(super::join_addresses(&[]), "");
+        let addresses = [
+            SocketAddr::from(([127, 0, 0, 1], 3000)),
+            SocketAddr::from(([0, 0, 0, 0, 0, 0, 0, 1], 3001)),
+        ];
+        assert_eq!(super::join_addresses(&addresses[..1]), "127.0.0.1:3000");
+        assert_eq!(
+            super::join_addresses(&addresses),
+            "127.0.0.1:3000,[::1]:3001"
+        );
+        Client::from_socket_addrs(0, &addresses, 1).unwrap();
+    }
+
+    #[test]
+    fn abort_pending() {
+        let client = Client::new(0, "3000", 1).unwrap();
+        let (sender, receiver) = oneshot::channel();
+        let reply = PendingReply::new(&client.pending, sender);
+
+        client.abort_pending();
+        assert!(PendingReplies::lock(&client.pending).senders.is_empty());
+        assert!(pollster::block_on(receiver).is_err());
+        assert!(reply.take().is_none());
+
+        // Later requests aren't affected
+        pollster::block_on(client.submit_raw(OperationKind::LookupAccounts.into(), vec![0; 16]))
+            .unwrap();
+    }
+
+    #[test]
+    fn dropped_pending_reply_is_removed() {
+        let client = Client::new(0, "3000", 1).unwrap();
+        let (sender, receiver) = oneshot::channel();
+        drop(PendingReply::new(&client.pending, sender));
+        assert!(PendingReplies::lock(&client.pending).senders.is_empty());
+        assert!(pollster::block_on(receiver).is_err());
+    }
+
+    #[test]
+    fn shutdown_timeout() {
+        let client = Client::new(0, "3000", 2).unwrap();
+        client.shutdown_timeout(Duration::ZERO).unwrap();
+
+        let client = Client::new(0, "3000", 2).unwrap();
+        // Stands in for a request tigerbeetle hasn't replied to yet
+        let _in_flight = client.sema.clone().try_acquire_owned().unwrap();
+        let timeout = Duration::from_millis(10);
+        let start = Instant::now();
+        let e = client.shutdown_timeout(timeout).unwrap_err();
+        assert!(start.elapsed() >= timeout);
+        assert_eq!(e.in_flight(), 1);
+    }
+
+    #[test]
+    fn batch_too_large() {
+        let client = Client::new(0, "3000", 1).unwrap();
+        let transfers = vec![Transfer::default(); MAX_BATCH_TRANSFERS + 1];
+        let e = pollster::block_on(client.create_transfers(transfers)).unwrap_err();
+        let CreateTransfersError::BatchTooLarge(e) = e else {
+            panic!("expected BatchTooLarge, got {e:?}");
+        };
+        assert_eq!(e.items(), MAX_BATCH_TRANSFERS + 1);
+        assert_eq!(e.max_items(), MAX_BATCH_TRANSFERS);
+
+        let accounts = vec![Account::default(); max_batch_len::<Account>() + 1];
+        let e = pollster::block_on(client.create_accounts(accounts)).unwrap_err();
+        assert!(matches!(e, CreateAccountsError::BatchTooLarge(_)));
+
+        let transfers = vec![Transfer::default(); MAX_BATCH_TRANSFERS];
+        let e = pollster::block_on(client.create_transfers(transfers));
+        assert!(!matches!(e, Err(CreateTransfersError::BatchTooLarge(_))));
+    }
+
+    #[test]
+    fn first_completion_hook_runs_once() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let client = ClientBuilder::new(0, "3000", 1)
+            .on_first_completion_thread({
+                let calls = calls.clone();
+                move || {
+                    calls.fetch_add(1, Ordering::Relaxed);
+                }
+            })
+            .build()
+            .unwrap();
+        for _ in 0..10 {
+            pollster::block_on(
+                client.submit_raw(OperationKind::LookupAccounts.into(), vec![0; 16]),
+            )
+            .unwrap();
+        }
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn first_completion_hook_panic_is_contained() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let client = ClientBuilder::new(0, "3000", 1)
+            .on_first_completion_thread({
+                let calls = calls.clone();
+                move || {
+                    calls.fetch_add(1, Ordering::Relaxed);
+                    panic!("first completion hook");
+                }
+            })
+            .build()
+            .unwrap();
+        for _ in 0..3 {
+            let reply = pollster::block_on(
+                client.submit_raw(OperationKind::LookupAccounts.into(), vec![1; 16]),
+            )
+            .unwrap();
+            assert_eq!(reply, [1; 16]);
+        }
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn empty_batch_no_op() {
+        let client = ClientBuilder::new(0, "3000", 1)
+            .with_empty_batch_behavior(EmptyBatchBehavior::NoOp)
+            .build()
+            .unwrap();
+        pollster::block_on(client.create_accounts(Vec::new())).unwrap();
+        pollster::block_on(client.create_transfers(Vec::new())).unwrap();
+    }
+
+    #[test]
+    fn empty_batch_error() {
+        let client = ClientBuilder::new(0, "3000", 1)
+            .with_empty_batch_behavior(EmptyBatchBehavior::Error)
+            .build()
+            .unwrap();
+        let e = pollster::block_on(client.create_accounts(Vec::new())).unwrap_err();
+        let CreateAccountsError::Send(e) = e else {
+            panic!("expected send error, got {e:?}");
+        };
+        assert!(matches!(e.kind(), SendErrorKind::InvalidDataSize));
+        let e = pollster::block_on(client.create_transfers(Vec::new())).unwrap_err();
+        let CreateTransfersError::Send(e) = e else {
+            panic!("expected send error, got {e:?}");
+        };
+        assert!(matches!(e.kind(), SendErrorKind::InvalidDataSize));
+    }
+
+    #[test]
+    fn create_transfers_echo_returns_submitted_batch() {
+        let client = Client::new(0, "3000", 1).unwrap();
+        let transfers: Arc<[Transfer]> = vec![Transfer::new(1), Transfer::new(2)].into();
+        let (echoed, _) =
+            pollster::block_on(client.create_transfers_echo(transfers.clone())).unwrap();
+        assert!(Arc::ptr_eq(&echoed, &transfers));
+        // Only the returned clone is left besides ours, request data is released
+        assert_eq!(Arc::strong_count(&transfers), 2);
+    }
+
+    #[test]
+    fn concurrency_max_zero() {
+        let Err(e) = Client::new(0, "3000", 0) else {
+            panic!("client created with zero concurrency_max");
+        };
+        assert!(matches!(
+            e.kind(),
+            NewClientErrorKind::ConcurrencyMaxInvalid
+        ));
+    }
+
+    #[test]
+    fn concurrency_above_max_is_clamped() {
+        for requested in [MAX_CONCURRENCY + 1, u32::MAX] {
+            let client = Client::new(0, "3000", requested).unwrap();
+            assert_eq!(client.concurrency_max(), requested);
+            assert_eq!(client.effective_concurrency(), MAX_CONCURRENCY);
+            assert_eq!(client.available_concurrency(), MAX_CONCURRENCY as usize);
+        }
+    }
+
+    #[test]
+    fn available_concurrency_recovers() {
+        let client = Client::new(0, "3000", 2).unwrap();
+        assert_eq!(client.available_concurrency(), 2);
+
+        let first = client.try_create_transfers(Vec::new()).ok().unwrap();
+        let second = client.try_create_transfers(Vec::new()).ok().unwrap();
+        assert_eq!(client.available_concurrency(), 0);
+        assert!(client.try_create_transfers(Vec::new()).is_err());
+
+        pollster::block_on(first).unwrap();
+        assert_eq!(client.available_concurrency(), 1);
+        drop(second);
+        assert_eq!(client.available_concurrency(), 2);
+    }
+}
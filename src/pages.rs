@@ -0,0 +1,65 @@
+use std::vec;
+
+use crate::{account, error::RequestError, Client};
+
+/// Balance history of an account fetched page by page, see
+/// [`Client::account_balances_stream`].
+///
+/// Pages are requested by advancing filter's `timestamp_min` past the last
+/// received balance. Balance snapshots are taken at transfer timestamps,
+/// which are unique within a cluster, so no snapshot is skipped or repeated
+/// at page boundaries.
+pub struct AccountBalancesPages<'a> {
+    client: &'a Client,
+    filter: account::Filter,
+    page: vec::IntoIter<account::Balance>,
+    done: bool,
+}
+
+impl<'a> AccountBalancesPages<'a> {
+    pub(crate) fn new(client: &'a Client, account_id: u128) -> Self {
+        AccountBalancesPages {
+            client,
+            filter: account::Filter::new(account_id, account::Filter::MAX_LIMIT)
+                .with_flags(account::FilterFlags::DEBITS | account::FilterFlags::CREDITS),
+            page: Vec::new().into_iter(),
+            done: false,
+        }
+    }
+
+    /// Get the next balance, requesting the next page if needed.
+    ///
+    /// Returns `Ok(None)` once history is exhausted, and right away for an
+    /// account without [`account::Flags::HISTORY`] or a missing one, since
+    /// tigerbeetle returns no balances for them.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RequestError`] if a page couldn't be requested. Call again to
+    /// retry the same page.
+    pub async fn next(&mut self) -> Result<Option<account::Balance>, RequestError> {
+        if let Some(balance) = self.page.next() {
+            return Ok(Some(balance));
+        }
+        if self.done {
+            return Ok(None);
+        }
+        let page = self
+            .client
+            .get_account_balances(Box::new(self.filter))
+            .await?;
+        if page.len() < self.filter.limit() as usize {
+            self.done = true;
+        }
+        // `timestamp_min` is inclusive, so start one nanosecond past the
+        // last balance to not receive it again
+        match page.last().map(|b| b.timestamp_nanos().checked_add(1)) {
+            Some(Some(next_min)) if next_min != u64::MAX => {
+                self.filter.set_timestamp_min_nanos(next_min);
+            }
+            _ => self.done = true,
+        }
+        self.page = page.into_iter();
+        Ok(self.page.next())
+    }
+}
@@ -1,11 +1,14 @@
+use std::{sync::Arc, time::Instant};
+
 use crate::{
     account,
-    core::OperationKind,
-    error::{CreateAccountsApiError, CreateTransfersApiError},
+    core::{decode, Operation, OperationKind},
+    error::{CreateAccountsApiError, CreateTransfersApiError, MalformedReply, SendError},
     Account, Transfer,
 };
 
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Reply {
     CreateAccounts(Result<(), CreateAccountsApiError>),
     CreateTransfers(Result<(), CreateTransfersApiError>),
@@ -13,82 +16,254 @@ pub enum Reply {
     GetAccountTransfers(Vec<Transfer>),
     LookupAccounts(Vec<Account>),
     LookupTransfers(Vec<Transfer>),
+    /// Reply to a known operation which isn't a whole number of items, kept
+    /// as an error instead of panicking on the tigerbeetle thread.
+    Malformed(MalformedReply),
+    /// Reply to an operation this client doesn't know, or to a request
+    /// submitted for raw reply.
+    Raw {
+        operation: u8,
+        payload: Vec<u8>,
+    },
 }
 
 impl Reply {
-    pub fn copy_from_reply(operation: OperationKind, payload: &[u8]) -> Self {
-        match operation {
-            OperationKind::CreateAccounts => {
-                let results = bytemuck::pod_collect_to_vec(payload);
-                let e = CreateAccountsApiError::from_raw_results(results);
-                Reply::CreateAccounts(e.map_or(Ok(()), Err))
-            }
-            OperationKind::CreateTransfers => {
-                let results = bytemuck::pod_collect_to_vec(payload);
-                let e = CreateTransfersApiError::from_raw_results(results);
-                Reply::CreateTransfers(e.map_or(Ok(()), Err))
-            }
-            OperationKind::GetAccountBalances => {
-                Reply::GetAccountBalances(bytemuck::pod_collect_to_vec(payload))
-            }
-            OperationKind::GetAccountTransfers => {
-                Reply::GetAccountTransfers(bytemuck::pod_collect_to_vec(payload))
-            }
+    pub fn copy_from_reply(operation: Operation, payload: &[u8]) -> Self {
+        let malformed = |e| Reply::Malformed(MalformedReply::from_decode_error(operation, e));
+        match operation.kind() {
+            OperationKind::CreateAccounts => match decode::create_accounts(payload) {
+                Ok(e) => Reply::CreateAccounts(e.map_or(Ok(()), Err)),
+                Err(e) => malformed(e),
+            },
+            OperationKind::CreateTransfers => match decode::create_transfers(payload) {
+                Ok(e) => Reply::CreateTransfers(e.map_or(Ok(()), Err)),
+                Err(e) => malformed(e),
+            },
+            OperationKind::GetAccountBalances => decode::get_account_balances(payload)
+                .map_or_else(malformed, Reply::GetAccountBalances),
+            OperationKind::GetAccountTransfers => decode::get_account_transfers(payload)
+                .map_or_else(malformed, Reply::GetAccountTransfers),
             OperationKind::LookupAccounts => {
-                Reply::LookupAccounts(bytemuck::pod_collect_to_vec(payload))
+                decode::lookup_accounts(payload).map_or_else(malformed, Reply::LookupAccounts)
             }
             OperationKind::LookupTransfers => {
-                Reply::LookupTransfers(bytemuck::pod_collect_to_vec(payload))
+                decode::lookup_transfers(payload).map_or_else(malformed, Reply::LookupTransfers)
             }
-            _ => unimplemented!("unknown operation kind"),
+            _ => Reply::Raw {
+                operation: operation.code(),
+                payload: payload.to_vec(),
+            },
+        }
+    }
+
+    /// # Errors
+    ///
+    /// Returns [`MalformedReply`] if the reply couldn't be decoded.
+    pub fn into_create_accounts(
+        self,
+    ) -> Result<Result<(), CreateAccountsApiError>, MalformedReply> {
+        match self {
+            Reply::CreateAccounts(out) => Ok(out),
+            Reply::Malformed(e) => Err(e),
+            _ => unreachable!("reply variant doesn't match CreateAccounts request: {self:?}"),
         }
     }
 
-    pub fn into_create_accounts(self) -> Result<(), CreateAccountsApiError> {
-        if let Reply::CreateAccounts(out) = self {
-            out
-        } else {
-            panic!("wrong reply variant, expected CreateAccounts but found: {self:?}")
+    /// # Errors
+    ///
+    /// Returns [`MalformedReply`] if the reply couldn't be decoded.
+    pub fn into_create_transfers(
+        self,
+    ) -> Result<Result<(), CreateTransfersApiError>, MalformedReply> {
+        match self {
+            Reply::CreateTransfers(out) => Ok(out),
+            Reply::Malformed(e) => Err(e),
+            _ => unreachable!("reply variant doesn't match CreateTransfers request: {self:?}"),
         }
     }
 
-    pub fn into_create_transfers(self) -> Result<(), CreateTransfersApiError> {
-        if let Reply::CreateTransfers(out) = self {
-            out
-        } else {
-            panic!("wrong reply variant, expected CreateTransfers but found: {self:?}")
+    /// # Errors
+    ///
+    /// Returns [`MalformedReply`] if the reply couldn't be decoded.
+    pub fn into_get_account_balances(self) -> Result<Vec<account::Balance>, MalformedReply> {
+        match self {
+            Reply::GetAccountBalances(out) => Ok(out),
+            Reply::Malformed(e) => Err(e),
+            _ => unreachable!("reply variant doesn't match GetAccountBalances request: {self:?}"),
         }
     }
 
-    pub fn into_get_account_balances(self) -> Vec<account::Balance> {
-        if let Reply::GetAccountBalances(out) = self {
-            out
-        } else {
-            panic!("wrong reply variant, expected GetAccountBalances but found: {self:?}")
+    /// # Errors
+    ///
+    /// Returns [`MalformedReply`] if the reply couldn't be decoded.
+    pub fn into_get_account_transfers(self) -> Result<Vec<Transfer>, MalformedReply> {
+        match self {
+            Reply::GetAccountTransfers(out) => Ok(out),
+            Reply::Malformed(e) => Err(e),
+            _ => unreachable!("reply variant doesn't match GetAccountTransfers request: {self:?}"),
         }
     }
 
-    pub fn into_get_account_transfers(self) -> Vec<Transfer> {
-        if let Reply::GetAccountTransfers(out) = self {
-            out
-        } else {
-            panic!("wrong reply variant, expected GetAccountTransfers but found: {self:?}")
+    /// # Errors
+    ///
+    /// Returns [`MalformedReply`] if the reply couldn't be decoded.
+    pub fn into_lookup_accounts(self) -> Result<Vec<Account>, MalformedReply> {
+        match self {
+            Reply::LookupAccounts(out) => Ok(out),
+            Reply::Malformed(e) => Err(e),
+            _ => unreachable!("reply variant doesn't match LookupAccounts request: {self:?}"),
         }
     }
 
-    pub fn into_lookup_accounts(self) -> Vec<Account> {
-        if let Reply::LookupAccounts(out) = self {
-            out
-        } else {
-            panic!("wrong reply variant, expected LookupAccounts but found: {self:?}")
+    /// # Errors
+    ///
+    /// Returns [`MalformedReply`] if the reply couldn't be decoded.
+    pub fn into_lookup_transfers(self) -> Result<Vec<Transfer>, MalformedReply> {
+        match self {
+            Reply::LookupTransfers(out) => Ok(out),
+            Reply::Malformed(e) => Err(e),
+            _ => unreachable!("reply variant doesn't match LookupTransfers request: {self:?}"),
         }
     }
 
-    pub fn into_lookup_transfers(self) -> Vec<Transfer> {
-        if let Reply::LookupTransfers(out) = self {
-            out
-        } else {
-            panic!("wrong reply variant, expected LookupTransfers but found: {self:?}")
+    pub fn into_raw(self) -> Vec<u8> {
+        match self {
+            Reply::Raw { payload, .. } => payload,
+            _ => unreachable!("reply variant doesn't match raw request: {self:?}"),
         }
     }
 }
+
+/// Metadata of a reply passed to [`ClientBuilder::on_reply`] hook.
+///
+/// [`ClientBuilder::on_reply`]: crate::ClientBuilder::on_reply
+#[derive(Clone, Copy, Debug)]
+pub struct ReplyMeta {
+    pub(crate) operation: Operation,
+    pub(crate) received_at: Instant,
+    pub(crate) status: Result<(), SendError>,
+}
+
+impl ReplyMeta {
+    /// Operation of the request this reply is for.
+    pub fn operation(&self) -> Operation {
+        self.operation
+    }
+
+    /// Moment the completion callback was called on the client.
+    pub fn received_at(&self) -> Instant {
+        self.received_at
+    }
+
+    /// Status of the request, the same one returned to the awaiting task.
+    pub fn status(&self) -> Result<(), SendError> {
+        self.status
+    }
+}
+
+/// Reply shared between many owners.
+///
+/// Cloning only bumps a reference count and never copies the data.
+pub struct SharedReply<T>(Arc<[T]>);
+
+impl<T> SharedReply<T> {
+    pub fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> Arc<[T]> {
+        self.0
+    }
+
+    /// Whether both replies point to the same data.
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Arc::ptr_eq(&this.0, &other.0)
+    }
+}
+
+impl<T> Clone for SharedReply<T> {
+    fn clone(&self) -> Self {
+        SharedReply(self.0.clone())
+    }
+}
+
+impl<T> std::ops::Deref for SharedReply<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> AsRef<[T]> for SharedReply<T> {
+    fn as_ref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for SharedReply<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("SharedReply")
+            .field(&self.as_slice())
+            .finish()
+    }
+}
+
+impl<T> From<Vec<T>> for SharedReply<T> {
+    fn from(value: Vec<T>) -> Self {
+        SharedReply(value.into())
+    }
+}
+
+impl<T> From<SharedReply<T>> for Arc<[T]> {
+    fn from(value: SharedReply<T>) -> Self {
+        value.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn malformed_reply() {
+        let operation = Operation::from(OperationKind::LookupAccounts);
+        let e = Reply::copy_from_reply(operation, &[0; 129])
+            .into_lookup_accounts()
+            .unwrap_err();
+        assert_eq!(e.operation().code(), operation.code());
+        assert_eq!(e.payload_len(), 129);
+
+        let e = Reply::copy_from_reply(OperationKind::CreateTransfers.into(), &[0; 4])
+            .into_create_transfers()
+            .unwrap_err();
+        assert_eq!(e.payload_len(), 4);
+    }
+
+    #[test]
+    fn empty_reply() {
+        let reply = Reply::copy_from_reply(OperationKind::LookupTransfers.into(), &[]);
+        assert!(reply.into_lookup_transfers().unwrap().is_empty());
+        let reply = Reply::copy_from_reply(OperationKind::CreateAccounts.into(), &[]);
+        assert!(reply.into_create_accounts().unwrap().is_ok());
+    }
+
+    #[test]
+    fn unknown_operation_is_raw() {
+        let reply = Reply::copy_from_reply(Operation::from_code(u8::MAX), &[1, 2, 3]);
+        assert_eq!(reply.into_raw(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn shared_reply_clones_share_data() {
+        let reply = SharedReply::from(vec![1u128, 2]);
+        let clone = reply.clone();
+        assert!(SharedReply::ptr_eq(&reply, &clone));
+        assert!(std::ptr::eq(reply.as_slice(), clone.as_slice()));
+        assert!(!SharedReply::ptr_eq(&reply, &SharedReply::from(vec![1, 2])));
+
+        let arc = clone.into_inner();
+        assert_eq!(Arc::strong_count(&arc), 2);
+        assert!(std::ptr::eq(&*arc, reply.as_slice()));
+    }
+}
@@ -0,0 +1,141 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+
+use crate::error::{SendError, SendErrorKind};
+
+/// Policy for retrying requests that failed with a [`SendError`].
+///
+/// Only send errors of the configured kinds are retried, nothing is retried
+/// by default. Bundled `tb_client` has no transient send errors, as every
+/// [`SendErrorKind`] reports a malformed request, see
+/// [`SendError::is_retriable`]. Api errors for individual accounts or
+/// transfers are deterministic and never retried.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    retry_on: Vec<SendErrorKind>,
+    max_attempts: u32,
+    max_elapsed: Duration,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Create policy retrying on send errors of given kinds with default
+    /// limits: 5 attempts, 10 seconds in total, backoff starting at 10
+    /// milliseconds and capped at 1 second.
+    pub fn new<I>(retry_on: I) -> Self
+    where
+        I: IntoIterator<Item = SendErrorKind>,
+    {
+        RetryPolicy {
+            retry_on: retry_on.into_iter().collect(),
+            max_attempts: 5,
+            max_elapsed: Duration::from_secs(10),
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_secs(1),
+        }
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+    /// Maximum number of attempts including the first one.
+    #[track_caller]
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        assert_ne!(max_attempts, 0, "max_attempts must not be zero");
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    pub fn max_elapsed(&self) -> Duration {
+        self.max_elapsed
+    }
+    /// Do not start another attempt if it would begin after this much time
+    /// since the first one.
+    pub fn with_max_elapsed(mut self, max_elapsed: Duration) -> Self {
+        self.max_elapsed = max_elapsed;
+        self
+    }
+
+    pub fn initial_backoff(&self) -> Duration {
+        self.initial_backoff
+    }
+    pub fn with_initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    pub fn max_backoff(&self) -> Duration {
+        self.max_backoff
+    }
+    pub fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Whether `error` is of a kind this policy retries on.
+    pub fn should_retry(&self, error: SendError) -> bool {
+        let code = error.kind() as u8;
+        self.retry_on.iter().any(|&k| k as u8 == code)
+    }
+
+    /// Delay before the attempt following `attempt`-th one (counting from 1).
+    ///
+    /// Backoff doubles every attempt up to [`Self::max_backoff`] with full
+    /// jitter applied.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exp = self
+            .initial_backoff
+            .saturating_mul(1 << attempt.saturating_sub(1).min(31))
+            .min(self.max_backoff);
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u32(attempt);
+        exp.mul_f64(hasher.finish() as f64 / u64::MAX as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::RetryPolicy;
+    use crate::error::{SendError, SendErrorKind};
+
+    #[test]
+    fn retries_only_listed_kinds() {
+        let too_much_data = SendError::from(SendErrorKind::TooMuchData);
+        let invalid_operation = SendError::from(SendErrorKind::InvalidOperation);
+
+        let policy = RetryPolicy::new([]);
+        assert!(!policy.should_retry(too_much_data));
+        assert!(!policy.should_retry(invalid_operation));
+
+        let policy = RetryPolicy::new([SendErrorKind::TooMuchData]);
+        assert!(policy.should_retry(too_much_data));
+        assert!(!policy.should_retry(invalid_operation));
+    }
+
+    #[test]
+    fn backoff_is_capped() {
+        let policy = RetryPolicy::new([])
+            .with_initial_backoff(Duration::from_millis(10))
+            .with_max_backoff(Duration::from_millis(100));
+        for attempt in 1..=64 {
+            let exp = Duration::from_millis(10 << (attempt - 1).min(4));
+            let backoff = policy.backoff(attempt);
+            assert!(
+                backoff <= exp.min(Duration::from_millis(100)),
+                "{backoff:?}"
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic = "max_attempts must not be zero"]
+    fn zero_attempts() {
+        let _ = RetryPolicy::new([]).with_max_attempts(0);
+    }
+}
@@ -13,6 +13,37 @@ use quote::quote;
 use syn::visit::Visit;
 
 const TIGERBEETLE_RELEASE: &str = "0.15.3";
+const ZIG_RELEASE: &str = "0.11.0";
+/// SHA-256 of zig release archives from https://ziglang.org/download/index.json,
+/// keyed by archive name, which holds both release and host.
+const ZIG_SHA256: &[(&str, &str)] = &[
+    (
+        "zig-linux-x86_64-0.11.0.tar.xz",
+        "2d00e789fec4f71790a6e7bf83ff91d564943c5ee843c5fd966efc474b423047",
+    ),
+    (
+        "zig-linux-aarch64-0.11.0.tar.xz",
+        "956eb095d8ba44ac6ebd27f7c9956e47d92937c103bf754745d0a39cdaa5d4c6",
+    ),
+    (
+        "zig-macos-x86_64-0.11.0.tar.xz",
+        "1c1c6b9a906b42baae73656e24e108fd8444bb50b6e8fd03e9e7a3f8b5f05686",
+    ),
+    (
+        "zig-macos-aarch64-0.11.0.tar.xz",
+        "c6ebf927bb13a707d74267474a9f553274e64906fd21bf1c75a20bde8cadf7b2",
+    ),
+    (
+        "zig-windows-x86_64-0.11.0.zip",
+        "142caa3b804d86b4752556c9b6b039b7517a08afa3af842645c7e2dcd125f652",
+    ),
+];
+const INSTALL_ZIG_HINT: &str = "downloading zig failed, if network access is \
+    unavailable set `TB_ZIG` to a zig executable or `TB_CLIENT_LIB_DIR` to a \
+    directory with prebuilt tb_client, see \
+    https://docs.rs/tigerbeetle-unofficial-sys";
+const SUBMODULE_HINT: &str = "tigerbeetle sources are missing, in a git checkout \
+    run `git submodule update --init` to fetch them";
 
 fn target_to_lib_dir(target: &str) -> Option<&'static str> {
     match target {
@@ -27,10 +58,16 @@ fn target_to_lib_dir(target: &str) -> Option<&'static str> {
     }
 }
 
-#[cfg(unix)]
-const SCRIPT_EXTENSION: &str = "sh";
-#[cfg(windows)]
-const SCRIPT_EXTENSION: &str = "bat";
+fn host_to_zig_host(host: &str) -> Option<&'static str> {
+    match host {
+        "aarch64-unknown-linux-gnu" | "aarch64-unknown-linux-musl" => Some("linux-aarch64"),
+        "aarch64-apple-darwin" => Some("macos-aarch64"),
+        "x86_64-unknown-linux-gnu" | "x86_64-unknown-linux-musl" => Some("linux-x86_64"),
+        "x86_64-apple-darwin" => Some("macos-x86_64"),
+        "x86_64-pc-windows-msvc" | "x86_64-pc-windows-gnu" => Some("windows-x86_64"),
+        _ => None,
+    }
+}
 
 fn main() {
     assert!(env!("CARGO_PKG_VERSION").ends_with(TIGERBEETLE_RELEASE));
@@ -38,12 +75,26 @@ fn main() {
     let debug: bool = env::var("DEBUG").unwrap().parse().unwrap();
     let target = env::var("TARGET").unwrap();
 
+    println!("cargo:rustc-env=TB_TIGERBEETLE_VERSION={TIGERBEETLE_RELEASE}");
+    println!("cargo:rustc-env=TB_ZIG_VERSION={ZIG_RELEASE}");
     println!("cargo:rerun-if-env-changed=DOCS_RS");
+    println!("cargo:rerun-if-env-changed=TB_CLIENT_LIB_DIR");
+    println!("cargo:rerun-if-env-changed=TB_ZIG");
+    println!("cargo:rerun-if-env-changed=TB_CLIENT_RELEASE");
     println!("cargo:rerun-if-changed=src/wrapper.h");
 
-    let wrapper;
-    if std::env::var("DOCS_RS").is_ok() {
-        wrapper = "src/wrapper.h".into();
+    let wrapper: Option<PathBuf>;
+    if env::var("CARGO_FEATURE_MOCK").is_ok() {
+        // `mock` feature provides `tb_client` functions in Rust, nothing to
+        // build, and uses pregenerated bindings, so neither libclang nor
+        // tigerbeetle sources are needed
+        wrapper = None;
+    } else if std::env::var("DOCS_RS").is_ok() {
+        wrapper = Some("src/wrapper.h".into());
+    } else if let Some(lib_dir) = env::var_os("TB_CLIENT_LIB_DIR") {
+        // Prebuilt library, bindings are generated from pregenerated header
+        link_tb_client(Path::new(&lib_dir), &target);
+        wrapper = Some("src/wrapper.h".into());
     } else {
         let target_lib_subdir = target_to_lib_dir(&target)
             .unwrap_or_else(|| panic!("target {target:?} is not supported"));
@@ -66,47 +117,32 @@ fn main() {
                 .collect(),
         );
 
-        let status = Command::new(
-            tigerbeetle_root
-                .join("scripts/install_zig")
-                .with_extension(SCRIPT_EXTENSION),
-        )
-        .current_dir(&tigerbeetle_root)
-        .status()
-        .expect("running install_zig script");
-        assert!(
-            status.success(),
-            "install_zig script failed with {status:?}"
-        );
+        // Missing in a git checkout without `tigerbeetle` submodule
+        if !tigerbeetle_root.join("build.zig").is_file() {
+            println!("cargo:warning={SUBMODULE_HINT}");
+            panic!("tigerbeetle sources not found at {tigerbeetle_root:?}\n{SUBMODULE_HINT}");
+        }
 
-        let status = Command::new(
-            tigerbeetle_root
-                .join("zig/zig")
-                .with_extension(env::consts::EXE_EXTENSION)
-                .canonicalize()
-                .unwrap(),
-        )
-        .arg("build")
-        .arg("c_client")
-        .args((!debug).then_some("-Drelease"))
-        .arg(format!("-Dtarget={target_lib_subdir}"))
-        .env("TIGERBEETLE_RELEASE", TIGERBEETLE_RELEASE)
-        .current_dir(&tigerbeetle_root)
-        .status()
-        .expect("running zig build subcommand");
+        let zig = match env::var_os("TB_ZIG") {
+            Some(zig) => PathBuf::from(zig),
+            None => install_zig(&out_dir, &env::var("HOST").unwrap()),
+        };
+
+        let status = Command::new(zig)
+            .arg("build")
+            .arg("c_client")
+            .args(zig_optimize_arg(debug))
+            .arg(format!("-Dtarget={target_lib_subdir}"))
+            .env("TIGERBEETLE_RELEASE", TIGERBEETLE_RELEASE)
+            .current_dir(&tigerbeetle_root)
+            .status()
+            .expect("running zig build subcommand");
         assert!(status.success(), "zig build failed with {status:?}");
 
         let lib_dir = tigerbeetle_root.join("src/clients/c/lib");
-        let link_search = lib_dir.join(target_lib_subdir);
-        println!(
-            "cargo:rustc-link-search=native={}",
-            link_search
-                .to_str()
-                .expect("link search directory path is not valid unicode")
-        );
-        println!("cargo:rustc-link-lib=static=tb_client");
+        link_tb_client(&lib_dir.join(target_lib_subdir), &target);
 
-        wrapper = lib_dir.join("include/wrapper.h");
+        let wrapper_path = lib_dir.join("include/wrapper.h");
         let generated_header = lib_dir.join("include/tb_client.h");
         assert!(
             std::fs::read_to_string(&generated_header).expect("reading generated `tb_client.h`")
@@ -114,28 +150,34 @@ fn main() {
                     .expect("reading pregenerated `tb_client.h`"),
             "generated and pregenerated `tb_client.h` headers must be equal, generated at: {generated_header:?}",
         );
-        std::fs::copy("src/wrapper.h", &wrapper).expect("copying wrapper.h");
+        std::fs::copy("src/wrapper.h", &wrapper_path).expect("copying wrapper.h");
+        wrapper = Some(wrapper_path);
     };
 
-    let bindings = bindgen::Builder::default()
-        .header(
-            wrapper
-                .to_str()
-                .expect("wrapper.h out path is not valid unicode"),
-        )
-        .default_enum_style(bindgen::EnumVariation::ModuleConsts)
-        .parse_callbacks(Box::new(TigerbeetleCallbacks {
-            out_dir: out_dir.clone(),
-        }))
-        .generate()
-        .expect("generating tb_client bindings");
+    let bindings = match wrapper {
+        Some(wrapper) => bindgen::Builder::default()
+            .header(
+                wrapper
+                    .to_str()
+                    .expect("wrapper.h out path is not valid unicode"),
+            )
+            .default_enum_style(bindgen::EnumVariation::ModuleConsts)
+            .parse_callbacks(Box::new(TigerbeetleCallbacks {
+                out_dir: out_dir.clone(),
+            }))
+            .generate()
+            .expect("generating tb_client bindings")
+            .to_string(),
+        None => {
+            println!("cargo:rerun-if-changed=src/mock_bindings.rs");
+            std::fs::read_to_string("src/mock_bindings.rs").expect("reading pregenerated bindings")
+        }
+    };
 
-    bindings
-        .write_to_file(out_dir.join("bindings.rs"))
-        .expect("writing tb_client bindings");
+    std::fs::write(out_dir.join("bindings.rs"), &bindings).expect("writing tb_client bindings");
 
     if std::env::var("CARGO_FEATURE_GENERATED_SAFE").is_ok() {
-        let bindings = syn::parse_file(&bindings.to_string()).unwrap();
+        let bindings = syn::parse_file(&bindings).unwrap();
 
         let mut visitor = TigerbeetleVisitor::default();
         visitor.visit_file(&bindings);
@@ -152,6 +194,208 @@ fn main() {
     }
 }
 
+/// Pick optimization mode of `tb_client`, following cargo profile unless
+/// overridden with `TB_CLIENT_RELEASE`.
+fn zig_optimize_arg(debug: bool) -> Option<&'static str> {
+    // Tigerbeetle's build.zig sets a preferred optimize mode, so zig only
+    // accepts `-Drelease` selecting ReleaseSafe, and no `-Doptimize`
+    match env::var("TB_CLIENT_RELEASE").as_deref() {
+        Ok("safe") => Some("-Drelease"),
+        Ok("fast") => panic!(
+            "`TB_CLIENT_RELEASE=fast` is not supported, tigerbeetle {TIGERBEETLE_RELEASE} \
+            build.zig only offers release-safe mode, unset it or set it to `safe`"
+        ),
+        Ok(other) => panic!("`TB_CLIENT_RELEASE` must be `safe`, got {other:?}"),
+        Err(_) => (!debug).then_some("-Drelease"),
+    }
+}
+
+/// Link `tb_client` from `dir`, statically unless `dynamic` feature is on.
+fn link_tb_client(dir: &Path, target: &str) {
+    let dir = dir
+        .to_str()
+        .expect("link search directory path is not valid unicode");
+    println!("cargo:rustc-link-search=native={dir}");
+    if env::var("CARGO_FEATURE_DYNAMIC").is_err() {
+        println!("cargo:rustc-link-lib=static=tb_client");
+        return;
+    }
+    println!("cargo:rustc-link-lib=dylib=tb_client");
+    // Only applies to this package's own tests and examples, dependents have
+    // to locate the library themselves, see crate docs
+    if !target.contains("windows") {
+        println!("cargo:rustc-link-arg=-Wl,-rpath,{dir}");
+    }
+}
+
+/// Download zig for `host` into `out_dir` unless it's there already, checking
+/// the archive against [`ZIG_SHA256`] before extracting it.
+fn install_zig(out_dir: &Path, host: &str) -> PathBuf {
+    use std::fmt::Write as _;
+
+    let zig_host = host_to_zig_host(host).unwrap_or_else(|| {
+        panic!("zig {ZIG_RELEASE} isn't available for host {host:?}\n{INSTALL_ZIG_HINT}")
+    });
+    let zig_dir_name = format!("zig-{zig_host}-{ZIG_RELEASE}");
+    let archive_name = if host.contains("windows") {
+        format!("{zig_dir_name}.zip")
+    } else {
+        format!("{zig_dir_name}.tar.xz")
+    };
+    let expected_sha256 = ZIG_SHA256
+        .iter()
+        .find_map(|&(name, sha256)| (name == archive_name).then_some(sha256))
+        .unwrap_or_else(|| {
+            panic!("no pinned SHA-256 of zig archive {archive_name:?}\n{INSTALL_ZIG_HINT}")
+        });
+
+    let zig_dir = out_dir.join(&zig_dir_name);
+    let zig = zig_dir
+        .join("zig")
+        .with_extension(env::consts::EXE_EXTENSION);
+    // Extracted zig is only moved into place once complete
+    if zig.is_file() {
+        return zig;
+    }
+
+    let url = format!("https://ziglang.org/download/{ZIG_RELEASE}/{archive_name}");
+    let archive = download(&url);
+    let sha256 = sha256(&archive).iter().fold(String::new(), |mut hex, b| {
+        write!(hex, "{b:02x}").unwrap();
+        hex
+    });
+    assert!(
+        sha256 == expected_sha256,
+        "SHA-256 of zig archive downloaded from {url} doesn't match the pinned one, \
+        expected {expected_sha256}, got {sha256}"
+    );
+
+    let extract_dir = out_dir.join("zig-extract");
+    if extract_dir.exists() {
+        std::fs::remove_dir_all(&extract_dir).expect("removing stale zig extraction");
+    }
+    std::fs::create_dir(&extract_dir).expect("creating zig extraction directory");
+    if archive_name.ends_with(".zip") {
+        zip::ZipArchive::new(io::Cursor::new(archive))
+            .and_then(|mut zip| zip.extract(&extract_dir))
+            .expect("extracting zig archive");
+    } else {
+        let archive_path = extract_dir.join(&archive_name);
+        std::fs::write(&archive_path, archive).expect("writing zig archive");
+        // Same as tigerbeetle's `install_zig` script, there's no xz decoder
+        // among build dependencies
+        let status = Command::new("tar")
+            .arg("-xf")
+            .arg(&archive_path)
+            .current_dir(&extract_dir)
+            .status()
+            .expect("running tar to extract zig archive");
+        assert!(
+            status.success(),
+            "extracting zig archive failed with {status:?}"
+        );
+        std::fs::remove_file(&archive_path).expect("removing zig archive");
+    }
+    std::fs::rename(extract_dir.join(&zig_dir_name), &zig_dir).expect("moving extracted zig");
+    std::fs::remove_dir_all(&extract_dir).expect("removing zig extraction directory");
+    zig
+}
+
+fn download(url: &str) -> Vec<u8> {
+    let mut data = Vec::new();
+    let mut easy = curl::easy::Easy::new();
+    easy.url(url).unwrap();
+    easy.follow_location(true).unwrap();
+    easy.fail_on_error(true).unwrap();
+    let mut transfer = easy.transfer();
+    transfer
+        .write_function(|chunk| {
+            data.extend_from_slice(chunk);
+            Ok(chunk.len())
+        })
+        .unwrap();
+    if let Err(e) = transfer.perform() {
+        println!("cargo:warning={INSTALL_ZIG_HINT}");
+        panic!("downloading {url}: {e}\n{INSTALL_ZIG_HINT}");
+    }
+    drop(transfer);
+    data
+}
+
+/// SHA-256 digest of `data`, following FIPS 180-4.
+fn sha256(data: &[u8]) -> [u8; 32] {
+    const K: [u32; 64] = [
+        0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4,
+        0xab1c5ed5, 0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe,
+        0x9bdc06a7, 0xc19bf174, 0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f,
+        0x4a7484aa, 0x5cb0a9dc, 0x76f988da, 0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7,
+        0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967, 0x27b70a85, 0x2e1b2138, 0x4d2c6dfc,
+        0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85, 0xa2bfe8a1, 0xa81a664b,
+        0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070, 0x19a4c116,
+        0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+        0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7,
+        0xc67178f2,
+    ];
+    let mut state: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+        0x5be0cd19,
+    ];
+
+    // Message is padded with a set bit, zeroes and its length in bits
+    let mut tail = data[data.len() / 64 * 64..].to_vec();
+    tail.push(0x80);
+    while tail.len() % 64 != 56 {
+        tail.push(0);
+    }
+    tail.extend_from_slice(&(data.len() as u64 * 8).to_be_bytes());
+
+    for block in data.chunks_exact(64).chain(tail.chunks_exact(64)) {
+        let mut w = [0u32; 64];
+        for (w, word) in w.iter_mut().zip(block.chunks_exact(4)) {
+            *w = u32::from_be_bytes(word.try_into().unwrap());
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[i - 7])
+                .wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+        for (k, w) in K.iter().zip(w) {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let t1 = h
+                .wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(*k)
+                .wrapping_add(w);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(maj);
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+        for (s, v) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *s = s.wrapping_add(v);
+        }
+    }
+
+    let mut digest = [0; 32];
+    for (out, s) in digest.chunks_exact_mut(4).zip(state) {
+        out.copy_from_slice(&s.to_be_bytes());
+    }
+    digest
+}
+
 #[derive(Default)]
 struct TigerbeetleVisitor {
     output: proc_macro2::TokenStream,
@@ -173,6 +417,7 @@ impl Visit<'_> for TigerbeetleVisitor {
             };
             let mut type_exists = false;
             let mut variants = Vec::new();
+            let mut variant_docs = BTreeMap::new();
             assert!(content.len() > 1);
             for item in content {
                 match item {
@@ -186,6 +431,9 @@ impl Visit<'_> for TigerbeetleVisitor {
                         };
                         let i = i.base10_parse::<u32>().unwrap();
                         variants.push((c.ident.to_string(), c.ident.clone(), i));
+                        if let Some(doc) = doc_comment(&c.attrs) {
+                            variant_docs.insert(c.ident.to_string(), doc);
+                        }
                     }
                     syn::Item::Type(t) if t.ident == "Type" && !type_exists => type_exists = true,
                     _ => break 'process,
@@ -333,6 +581,68 @@ impl Visit<'_> for TigerbeetleVisitor {
                         Self::UnstableUncategorized => unimplemented!("variant is not supported yet")
                     )));
 
+                let description_fn = errorize.then(|| {
+                    let branches = variants
+                        .iter()
+                        .map(|(s, v, _)| {
+                            let n = syn::Ident::new(s, v.span());
+                            let d = variant_docs
+                                .get(&v.to_string())
+                                .cloned()
+                                .unwrap_or_else(|| camel_case_into_snake_case(s).replace('_', " "));
+                            quote!(Self:: #n => #d)
+                        })
+                        .chain(std::iter::once(quote!(
+                            Self::UnstableUncategorized => "unknown error"
+                        )));
+                    let doc = format!(
+                        "Returns a static human readable description of [`{new_enum_name}`] variant"
+                    );
+                    quote! {
+                        #[doc = #doc]
+                        pub fn description(self) -> &'static str {
+                            match self {
+                                #(#branches),*
+                            }
+                        }
+                    }
+                });
+
+                let code_fns = (!errorize).then(|| {
+                    let branches = variants
+                        .iter()
+                        .map(|(n, v, _)| {
+                            let n = syn::Ident::new(n, v.span());
+                            quote!(super:: #enum_ident :: #v => Some(Self:: #n))
+                        })
+                        .chain(std::iter::once(quote!(_ => None)));
+                    let from_code_doc = format!(
+                        "Returns [`{new_enum_name}`] variant with raw code `code`, or `None` if the code is unknown"
+                    );
+                    quote! {
+                        #[doc = "Returns raw code of this variant"]
+                        #[doc = ""]
+                        #[doc = "Panics on hidden `UnstableUncategorized` variant, which has no code."]
+                        pub fn code(self) -> #repr_type {
+                            match self {
+                                Self::UnstableUncategorized => panic!("variant has no code"),
+                                v => v as #repr_type,
+                            }
+                        }
+
+                        #[doc = #from_code_doc]
+                        #[doc = ""]
+                        #[doc = "# Stability"]
+                        #[doc = ""]
+                        #[doc = "Might return `Some` instead of `None` after a minor version bump"]
+                        pub fn from_code(code: #repr_type) -> Option<Self> {
+                            match code as u32 {
+                                #(#branches),*
+                            }
+                        }
+                    }
+                });
+
                 let variants = variants
                     .iter()
                     .map(|(n, v, _)| {
@@ -376,6 +686,10 @@ impl Visit<'_> for TigerbeetleVisitor {
                                 #(#into_snake_case_str_branches),*
                             }
                         }
+
+                        #description_fn
+
+                        #code_fns
                     }
                 });
                 self.output.extend(extra);
@@ -491,6 +805,27 @@ impl bindgen::callbacks::ParseCallbacks for TigerbeetleCallbacks {
     }
 }
 
+fn doc_comment(attrs: &[syn::Attribute]) -> Option<String> {
+    let lines: Vec<_> = attrs
+        .iter()
+        .filter(|a| a.path().is_ident("doc"))
+        .filter_map(|a| match &a.meta {
+            syn::Meta::NameValue(syn::MetaNameValue {
+                value:
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(s),
+                        ..
+                    }),
+                ..
+            }) => Some(s.value()),
+            _ => None,
+        })
+        .map(|l| l.trim().to_owned())
+        .filter(|l| !l.is_empty())
+        .collect();
+    (!lines.is_empty()).then(|| lines.join(" "))
+}
+
 fn screaming_snake_case_into_camel_case(src: &str) -> String {
     let mut dst = String::with_capacity(src.len());
     for word in src.split('_') {
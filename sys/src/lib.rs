@@ -1,3 +1,31 @@
+//! Native bindings to `tb_client`, tigerbeetle's C client library.
+//!
+//! # Building and linking
+//!
+//! By default `tb_client` is built from the bundled tigerbeetle sources with
+//! zig and linked statically. Set `TB_CLIENT_LIB_DIR` to a directory with an
+//! already built `tb_client` of the same tigerbeetle release to skip building
+//! it.
+//!
+//! Zig is downloaded from ziglang.org by the build script, which fails the
+//! build unless the archive matches the SHA-256 pinned for the host. To use
+//! some other zig toolchain, or to build without network access, set `TB_ZIG`
+//! to its `zig` executable. It has to be the version from [`ZIG_VERSION`].
+//!
+//! `tb_client` is built in debug mode for debug cargo profiles and in
+//! release-safe mode otherwise. Set `TB_CLIENT_RELEASE` to `safe` to always
+//! build it in release-safe mode, as client performance is mostly determined
+//! by it. Release-fast mode can't be selected, since tigerbeetle's
+//! `build.zig` only offers release-safe one, so `TB_CLIENT_RELEASE=fast`
+//! fails the build.
+//!
+//! With `dynamic` feature `tb_client` is linked as a shared library
+//! (`libtb_client.so`, `libtb_client.dylib` or `tb_client.dll`), which then
+//! has to be found at runtime. Build scripts can't set rpath of dependent
+//! binaries, so either install the library where the dynamic loader looks
+//! for it, point `LD_LIBRARY_PATH` (linux), `DYLD_LIBRARY_PATH` (macos) or
+//! `PATH` (windows) at its directory, or set rpath yourself, e.g. with
+//! `RUSTFLAGS="-C link-arg=-Wl,-rpath,$TB_CLIENT_LIB_DIR"`.
 #![allow(non_upper_case_globals)]
 #![allow(non_camel_case_types)]
 #![allow(non_snake_case)]
@@ -5,10 +33,42 @@
 
 include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
 
+#[cfg(feature = "mock")]
+mod mock;
+
+/// Version of tigerbeetle the client library is built from.
+pub const TIGERBEETLE_VERSION: &str = env!("TB_TIGERBEETLE_VERSION");
+/// Version of zig toolchain the client library is built with.
+pub const ZIG_VERSION: &str = env!("TB_ZIG_VERSION");
+
+/// Maximum size of a message in bytes including its header, mirrors
+/// `message_size_max` from tigerbeetle's `src/constants.zig`.
+pub const MESSAGE_SIZE_MAX: usize = 1024 * 1024;
+/// Size of a message header in bytes.
+pub const MESSAGE_HEADER_SIZE: usize = 256;
+/// Maximum size of request data or reply payload in bytes.
+pub const MESSAGE_BODY_SIZE_MAX: usize = MESSAGE_SIZE_MAX - MESSAGE_HEADER_SIZE;
+
 /// Available only with `generated-safe` feature
 #[cfg(feature = "generated-safe")]
 #[allow(clippy::unnecessary_cast, clippy::assign_op_pattern)]
 #[doc(hidden)]
 pub mod generated_safe {
     include!(concat!(env!("OUT_DIR"), "/generated.rs"));
+
+    impl PacketStatusErrorKind {
+        /// Whether resending the same request could succeed.
+        ///
+        /// Every packet status error is reported for a malformed request, so
+        /// resending it fails the same way. Unknown kinds aren't retriable
+        /// either.
+        pub fn is_retriable(self) -> bool {
+            match self {
+                Self::TooMuchData
+                | Self::InvalidOperation
+                | Self::InvalidDataSize
+                | Self::UnstableUncategorized => false,
+            }
+        }
+    }
 }
@@ -0,0 +1,190 @@
+//! Pure Rust stand-in for `tb_client`, enabled with `mock` feature.
+//!
+//! Nothing is built with zig nor linked, so code using these bindings can run
+//! under Miri. Bindings are pregenerated in `mock_bindings.rs`, so neither
+//! libclang nor tigerbeetle sources are needed to build either.
+//!
+//! Client never connects anywhere: every submitted packet is completed right
+//! inside `tb_client_submit` with its own data echoed back as the reply, like
+//! a client created with `tb_client_init_echo`. Replies are request bytes, so
+//! don't rely on them decoding as results of the operation. Requests larger
+//! than [`MESSAGE_BODY_SIZE_MAX`](crate::MESSAGE_BODY_SIZE_MAX) fail with
+//! `TB_PACKET_TOO_MUCH_DATA` status, same as with `tb_client`.
+#![allow(improper_ctypes_definitions)] // u128
+
+use std::{
+    ffi::{c_char, c_void},
+    ptr,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use super::{
+    tb_client_t, tb_packet_t, tb_uint128_t, TB_PACKET_ACQUIRE_STATUS, TB_PACKET_STATUS, TB_STATUS,
+};
+
+type OnCompletionFn = unsafe extern "C" fn(usize, tb_client_t, *mut tb_packet_t, *const u8, u32);
+
+struct MockClient {
+    on_completion_ctx: usize,
+    on_completion_fn: OnCompletionFn,
+    packets_left: AtomicU32,
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn tb_client_init(
+    out_client: *mut tb_client_t,
+    cluster_id: tb_uint128_t,
+    address_ptr: *const c_char,
+    address_len: u32,
+    packets_count: u32,
+    on_completion_ctx: usize,
+    on_completion_fn: Option<OnCompletionFn>,
+) -> TB_STATUS::Type {
+    tb_client_init_echo(
+        out_client,
+        cluster_id,
+        address_ptr,
+        address_len,
+        packets_count,
+        on_completion_ctx,
+        on_completion_fn,
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn tb_client_init_echo(
+    out_client: *mut tb_client_t,
+    _cluster_id: tb_uint128_t,
+    _address_ptr: *const c_char,
+    address_len: u32,
+    packets_count: u32,
+    on_completion_ctx: usize,
+    on_completion_fn: Option<OnCompletionFn>,
+) -> TB_STATUS::Type {
+    if address_len == 0 {
+        return TB_STATUS::TB_STATUS_ADDRESS_INVALID;
+    }
+    if packets_count == 0 {
+        return TB_STATUS::TB_STATUS_CONCURRENCY_MAX_INVALID;
+    }
+    let Some(on_completion_fn) = on_completion_fn else {
+        return TB_STATUS::TB_STATUS_UNEXPECTED;
+    };
+    let client = Box::new(MockClient {
+        on_completion_ctx,
+        on_completion_fn,
+        packets_left: AtomicU32::new(packets_count),
+    });
+    out_client.write(Box::into_raw(client).cast());
+    TB_STATUS::TB_STATUS_SUCCESS
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn tb_client_acquire_packet(
+    client: tb_client_t,
+    out_packet: *mut *mut tb_packet_t,
+) -> TB_PACKET_ACQUIRE_STATUS::Type {
+    let client = &*client.cast::<MockClient>();
+    let acquired = client
+        .packets_left
+        .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| n.checked_sub(1))
+        .is_ok();
+    if !acquired {
+        return TB_PACKET_ACQUIRE_STATUS::TB_PACKET_ACQUIRE_CONCURRENCY_MAX_EXCEEDED;
+    }
+    let packet = Box::new(tb_packet_t {
+        next: ptr::null_mut(),
+        user_data: ptr::null_mut::<c_void>(),
+        operation: 0,
+        status: 0,
+        data_size: 0,
+        data: ptr::null_mut(),
+    });
+    out_packet.write(Box::into_raw(packet));
+    TB_PACKET_ACQUIRE_STATUS::TB_PACKET_ACQUIRE_OK
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn tb_client_release_packet(client: tb_client_t, packet: *mut tb_packet_t) {
+    let client = &*client.cast::<MockClient>();
+    drop(Box::from_raw(packet));
+    client.packets_left.fetch_add(1, Ordering::AcqRel);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn tb_client_submit(client: tb_client_t, packet: *mut tb_packet_t) {
+    let mock = &*client.cast::<MockClient>();
+    let (data, data_size) = ((*packet).data.cast_const().cast(), (*packet).data_size);
+    // Like `tb_client`, reject requests not fitting into a single message
+    if data_size as usize > crate::MESSAGE_BODY_SIZE_MAX {
+        (*packet).status = TB_PACKET_STATUS::TB_PACKET_TOO_MUCH_DATA as u8;
+        (mock.on_completion_fn)(mock.on_completion_ctx, client, packet, ptr::null(), 0);
+        return;
+    }
+    (mock.on_completion_fn)(mock.on_completion_ctx, client, packet, data, data_size);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn tb_client_deinit(client: tb_client_t) {
+    drop(Box::from_raw(client.cast::<MockClient>()));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{ptr, slice};
+
+    use crate::{
+        tb_client_acquire_packet, tb_client_deinit, tb_client_init, tb_client_release_packet,
+        tb_client_submit, tb_client_t, tb_packet_t, TB_OPERATION, TB_PACKET_ACQUIRE_STATUS,
+        TB_STATUS,
+    };
+
+    unsafe extern "C" fn on_completion(
+        ctx: usize,
+        _client: tb_client_t,
+        _packet: *mut tb_packet_t,
+        data: *const u8,
+        data_size: u32,
+    ) {
+        let reply = &mut *(ctx as *mut Vec<u8>);
+        reply.extend_from_slice(slice::from_raw_parts(data, data_size as usize));
+    }
+
+    #[test]
+    fn echo_round_trip() {
+        let mut reply = Vec::<u8>::new();
+        let mut data = [1u8, 2, 3];
+        unsafe {
+            let mut client = ptr::null_mut();
+            let address = "3000";
+            let status = tb_client_init(
+                &mut client,
+                0,
+                address.as_ptr().cast(),
+                address.len() as u32,
+                1,
+                ptr::addr_of_mut!(reply) as usize,
+                Some(on_completion),
+            );
+            assert_eq!(status, TB_STATUS::TB_STATUS_SUCCESS);
+
+            let mut packet = ptr::null_mut();
+            let status = tb_client_acquire_packet(client, &mut packet);
+            assert_eq!(status, TB_PACKET_ACQUIRE_STATUS::TB_PACKET_ACQUIRE_OK);
+            let mut other = ptr::null_mut();
+            let status = tb_client_acquire_packet(client, &mut other);
+            assert_eq!(
+                status,
+                TB_PACKET_ACQUIRE_STATUS::TB_PACKET_ACQUIRE_CONCURRENCY_MAX_EXCEEDED
+            );
+
+            (*packet).operation = TB_OPERATION::TB_OPERATION_LOOKUP_ACCOUNTS as u8;
+            (*packet).data = data.as_mut_ptr().cast();
+            (*packet).data_size = data.len() as u32;
+            tb_client_submit(client, packet);
+            tb_client_release_packet(client, packet);
+            tb_client_deinit(client);
+        }
+        assert_eq!(reply, data);
+    }
+}
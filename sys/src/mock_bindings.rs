@@ -0,0 +1,265 @@
+// Bindings of `tb_client.h` pregenerated for `mock` feature, so that mock
+// builds need neither libclang nor tigerbeetle sources. Keep in sync with the
+// header of the bundled tigerbeetle release.
+
+pub type tb_uint128_t = u128;
+pub mod TB_ACCOUNT_FLAGS {
+    pub type Type = ::std::os::raw::c_uint;
+    pub const TB_ACCOUNT_LINKED: Type = 1;
+    pub const TB_ACCOUNT_DEBITS_MUST_NOT_EXCEED_CREDITS: Type = 2;
+    pub const TB_ACCOUNT_CREDITS_MUST_NOT_EXCEED_DEBITS: Type = 4;
+    pub const TB_ACCOUNT_HISTORY: Type = 8;
+}
+pub mod TB_TRANSFER_FLAGS {
+    pub type Type = ::std::os::raw::c_uint;
+    pub const TB_TRANSFER_LINKED: Type = 1;
+    pub const TB_TRANSFER_PENDING: Type = 2;
+    pub const TB_TRANSFER_POST_PENDING_TRANSFER: Type = 4;
+    pub const TB_TRANSFER_VOID_PENDING_TRANSFER: Type = 8;
+    pub const TB_TRANSFER_BALANCING_DEBIT: Type = 16;
+    pub const TB_TRANSFER_BALANCING_CREDIT: Type = 32;
+}
+pub mod TB_CREATE_ACCOUNT_RESULT {
+    pub type Type = ::std::os::raw::c_uint;
+    pub const TB_CREATE_ACCOUNT_OK: Type = 0;
+    pub const TB_CREATE_ACCOUNT_LINKED_EVENT_FAILED: Type = 1;
+    pub const TB_CREATE_ACCOUNT_LINKED_EVENT_CHAIN_OPEN: Type = 2;
+    pub const TB_CREATE_ACCOUNT_TIMESTAMP_MUST_BE_ZERO: Type = 3;
+    pub const TB_CREATE_ACCOUNT_RESERVED_FIELD: Type = 4;
+    pub const TB_CREATE_ACCOUNT_RESERVED_FLAG: Type = 5;
+    pub const TB_CREATE_ACCOUNT_ID_MUST_NOT_BE_ZERO: Type = 6;
+    pub const TB_CREATE_ACCOUNT_ID_MUST_NOT_BE_INT_MAX: Type = 7;
+    pub const TB_CREATE_ACCOUNT_FLAGS_ARE_MUTUALLY_EXCLUSIVE: Type = 8;
+    pub const TB_CREATE_ACCOUNT_DEBITS_PENDING_MUST_BE_ZERO: Type = 9;
+    pub const TB_CREATE_ACCOUNT_DEBITS_POSTED_MUST_BE_ZERO: Type = 10;
+    pub const TB_CREATE_ACCOUNT_CREDITS_PENDING_MUST_BE_ZERO: Type = 11;
+    pub const TB_CREATE_ACCOUNT_CREDITS_POSTED_MUST_BE_ZERO: Type = 12;
+    pub const TB_CREATE_ACCOUNT_LEDGER_MUST_NOT_BE_ZERO: Type = 13;
+    pub const TB_CREATE_ACCOUNT_CODE_MUST_NOT_BE_ZERO: Type = 14;
+    pub const TB_CREATE_ACCOUNT_EXISTS_WITH_DIFFERENT_FLAGS: Type = 15;
+    pub const TB_CREATE_ACCOUNT_EXISTS_WITH_DIFFERENT_USER_DATA_128: Type = 16;
+    pub const TB_CREATE_ACCOUNT_EXISTS_WITH_DIFFERENT_USER_DATA_64: Type = 17;
+    pub const TB_CREATE_ACCOUNT_EXISTS_WITH_DIFFERENT_USER_DATA_32: Type = 18;
+    pub const TB_CREATE_ACCOUNT_EXISTS_WITH_DIFFERENT_LEDGER: Type = 19;
+    pub const TB_CREATE_ACCOUNT_EXISTS_WITH_DIFFERENT_CODE: Type = 20;
+    pub const TB_CREATE_ACCOUNT_EXISTS: Type = 21;
+}
+pub mod TB_CREATE_TRANSFER_RESULT {
+    pub type Type = ::std::os::raw::c_uint;
+    pub const TB_CREATE_TRANSFER_OK: Type = 0;
+    pub const TB_CREATE_TRANSFER_LINKED_EVENT_FAILED: Type = 1;
+    pub const TB_CREATE_TRANSFER_LINKED_EVENT_CHAIN_OPEN: Type = 2;
+    pub const TB_CREATE_TRANSFER_TIMESTAMP_MUST_BE_ZERO: Type = 3;
+    pub const TB_CREATE_TRANSFER_RESERVED_FLAG: Type = 4;
+    pub const TB_CREATE_TRANSFER_ID_MUST_NOT_BE_ZERO: Type = 5;
+    pub const TB_CREATE_TRANSFER_ID_MUST_NOT_BE_INT_MAX: Type = 6;
+    pub const TB_CREATE_TRANSFER_FLAGS_ARE_MUTUALLY_EXCLUSIVE: Type = 7;
+    pub const TB_CREATE_TRANSFER_DEBIT_ACCOUNT_ID_MUST_NOT_BE_ZERO: Type = 8;
+    pub const TB_CREATE_TRANSFER_DEBIT_ACCOUNT_ID_MUST_NOT_BE_INT_MAX: Type = 9;
+    pub const TB_CREATE_TRANSFER_CREDIT_ACCOUNT_ID_MUST_NOT_BE_ZERO: Type = 10;
+    pub const TB_CREATE_TRANSFER_CREDIT_ACCOUNT_ID_MUST_NOT_BE_INT_MAX: Type = 11;
+    pub const TB_CREATE_TRANSFER_ACCOUNTS_MUST_BE_DIFFERENT: Type = 12;
+    pub const TB_CREATE_TRANSFER_PENDING_ID_MUST_BE_ZERO: Type = 13;
+    pub const TB_CREATE_TRANSFER_PENDING_ID_MUST_NOT_BE_ZERO: Type = 14;
+    pub const TB_CREATE_TRANSFER_PENDING_ID_MUST_NOT_BE_INT_MAX: Type = 15;
+    pub const TB_CREATE_TRANSFER_PENDING_ID_MUST_BE_DIFFERENT: Type = 16;
+    pub const TB_CREATE_TRANSFER_TIMEOUT_RESERVED_FOR_PENDING_TRANSFER: Type = 17;
+    pub const TB_CREATE_TRANSFER_AMOUNT_MUST_NOT_BE_ZERO: Type = 18;
+    pub const TB_CREATE_TRANSFER_LEDGER_MUST_NOT_BE_ZERO: Type = 19;
+    pub const TB_CREATE_TRANSFER_CODE_MUST_NOT_BE_ZERO: Type = 20;
+    pub const TB_CREATE_TRANSFER_DEBIT_ACCOUNT_NOT_FOUND: Type = 21;
+    pub const TB_CREATE_TRANSFER_CREDIT_ACCOUNT_NOT_FOUND: Type = 22;
+    pub const TB_CREATE_TRANSFER_ACCOUNTS_MUST_HAVE_THE_SAME_LEDGER: Type = 23;
+    pub const TB_CREATE_TRANSFER_TRANSFER_MUST_HAVE_THE_SAME_LEDGER_AS_ACCOUNTS: Type = 24;
+    pub const TB_CREATE_TRANSFER_PENDING_TRANSFER_NOT_FOUND: Type = 25;
+    pub const TB_CREATE_TRANSFER_PENDING_TRANSFER_NOT_PENDING: Type = 26;
+    pub const TB_CREATE_TRANSFER_PENDING_TRANSFER_HAS_DIFFERENT_DEBIT_ACCOUNT_ID: Type = 27;
+    pub const TB_CREATE_TRANSFER_PENDING_TRANSFER_HAS_DIFFERENT_CREDIT_ACCOUNT_ID: Type = 28;
+    pub const TB_CREATE_TRANSFER_PENDING_TRANSFER_HAS_DIFFERENT_LEDGER: Type = 29;
+    pub const TB_CREATE_TRANSFER_PENDING_TRANSFER_HAS_DIFFERENT_CODE: Type = 30;
+    pub const TB_CREATE_TRANSFER_EXCEEDS_PENDING_TRANSFER_AMOUNT: Type = 31;
+    pub const TB_CREATE_TRANSFER_PENDING_TRANSFER_HAS_DIFFERENT_AMOUNT: Type = 32;
+    pub const TB_CREATE_TRANSFER_PENDING_TRANSFER_ALREADY_POSTED: Type = 33;
+    pub const TB_CREATE_TRANSFER_PENDING_TRANSFER_ALREADY_VOIDED: Type = 34;
+    pub const TB_CREATE_TRANSFER_PENDING_TRANSFER_EXPIRED: Type = 35;
+    pub const TB_CREATE_TRANSFER_EXISTS_WITH_DIFFERENT_FLAGS: Type = 36;
+    pub const TB_CREATE_TRANSFER_EXISTS_WITH_DIFFERENT_DEBIT_ACCOUNT_ID: Type = 37;
+    pub const TB_CREATE_TRANSFER_EXISTS_WITH_DIFFERENT_CREDIT_ACCOUNT_ID: Type = 38;
+    pub const TB_CREATE_TRANSFER_EXISTS_WITH_DIFFERENT_AMOUNT: Type = 39;
+    pub const TB_CREATE_TRANSFER_EXISTS_WITH_DIFFERENT_PENDING_ID: Type = 40;
+    pub const TB_CREATE_TRANSFER_EXISTS_WITH_DIFFERENT_USER_DATA_128: Type = 41;
+    pub const TB_CREATE_TRANSFER_EXISTS_WITH_DIFFERENT_USER_DATA_64: Type = 42;
+    pub const TB_CREATE_TRANSFER_EXISTS_WITH_DIFFERENT_USER_DATA_32: Type = 43;
+    pub const TB_CREATE_TRANSFER_EXISTS_WITH_DIFFERENT_TIMEOUT: Type = 44;
+    pub const TB_CREATE_TRANSFER_EXISTS_WITH_DIFFERENT_CODE: Type = 45;
+    pub const TB_CREATE_TRANSFER_EXISTS: Type = 46;
+    pub const TB_CREATE_TRANSFER_OVERFLOWS_DEBITS_PENDING: Type = 47;
+    pub const TB_CREATE_TRANSFER_OVERFLOWS_CREDITS_PENDING: Type = 48;
+    pub const TB_CREATE_TRANSFER_OVERFLOWS_DEBITS_POSTED: Type = 49;
+    pub const TB_CREATE_TRANSFER_OVERFLOWS_CREDITS_POSTED: Type = 50;
+    pub const TB_CREATE_TRANSFER_OVERFLOWS_DEBITS: Type = 51;
+    pub const TB_CREATE_TRANSFER_OVERFLOWS_CREDITS: Type = 52;
+    pub const TB_CREATE_TRANSFER_OVERFLOWS_TIMEOUT: Type = 53;
+    pub const TB_CREATE_TRANSFER_EXCEEDS_CREDITS: Type = 54;
+    pub const TB_CREATE_TRANSFER_EXCEEDS_DEBITS: Type = 55;
+}
+pub mod TB_OPERATION {
+    pub type Type = ::std::os::raw::c_uint;
+    pub const TB_OPERATION_PULSE: Type = 128;
+    pub const TB_OPERATION_CREATE_ACCOUNTS: Type = 129;
+    pub const TB_OPERATION_CREATE_TRANSFERS: Type = 130;
+    pub const TB_OPERATION_LOOKUP_ACCOUNTS: Type = 131;
+    pub const TB_OPERATION_LOOKUP_TRANSFERS: Type = 132;
+    pub const TB_OPERATION_GET_ACCOUNT_TRANSFERS: Type = 133;
+    pub const TB_OPERATION_GET_ACCOUNT_BALANCES: Type = 134;
+}
+pub mod TB_PACKET_STATUS {
+    pub type Type = ::std::os::raw::c_uint;
+    pub const TB_PACKET_OK: Type = 0;
+    pub const TB_PACKET_TOO_MUCH_DATA: Type = 1;
+    pub const TB_PACKET_INVALID_OPERATION: Type = 2;
+    pub const TB_PACKET_INVALID_DATA_SIZE: Type = 3;
+}
+pub mod TB_PACKET_ACQUIRE_STATUS {
+    pub type Type = ::std::os::raw::c_uint;
+    pub const TB_PACKET_ACQUIRE_OK: Type = 0;
+    pub const TB_PACKET_ACQUIRE_CONCURRENCY_MAX_EXCEEDED: Type = 1;
+    pub const TB_PACKET_ACQUIRE_SHUTDOWN: Type = 2;
+}
+pub mod TB_ACCOUNT_FILTER_FLAGS {
+    pub type Type = ::std::os::raw::c_uint;
+    pub const TB_ACCOUNT_FILTER_DEBITS: Type = 1;
+    pub const TB_ACCOUNT_FILTER_CREDITS: Type = 2;
+    pub const TB_ACCOUNT_FILTER_REVERSED: Type = 4;
+}
+pub mod TB_STATUS {
+    pub type Type = ::std::os::raw::c_uint;
+    pub const TB_STATUS_SUCCESS: Type = 0;
+    pub const TB_STATUS_UNEXPECTED: Type = 1;
+    pub const TB_STATUS_OUT_OF_MEMORY: Type = 2;
+    pub const TB_STATUS_ADDRESS_INVALID: Type = 3;
+    pub const TB_STATUS_ADDRESS_LIMIT_EXCEEDED: Type = 4;
+    pub const TB_STATUS_CONCURRENCY_MAX_INVALID: Type = 5;
+    pub const TB_STATUS_SYSTEM_RESOURCES: Type = 6;
+    pub const TB_STATUS_NETWORK_SUBSYSTEM: Type = 7;
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, ::bytemuck::Pod, ::bytemuck::Zeroable)]
+pub struct tb_account_t {
+    pub id: tb_uint128_t,
+    pub debits_pending: tb_uint128_t,
+    pub debits_posted: tb_uint128_t,
+    pub credits_pending: tb_uint128_t,
+    pub credits_posted: tb_uint128_t,
+    pub user_data_128: tb_uint128_t,
+    pub user_data_64: u64,
+    pub user_data_32: u32,
+    pub reserved: u32,
+    pub ledger: u32,
+    pub code: u16,
+    pub flags: u16,
+    pub timestamp: u64,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, ::bytemuck::Pod, ::bytemuck::Zeroable)]
+pub struct tb_transfer_t {
+    pub id: tb_uint128_t,
+    pub debit_account_id: tb_uint128_t,
+    pub credit_account_id: tb_uint128_t,
+    pub amount: tb_uint128_t,
+    pub pending_id: tb_uint128_t,
+    pub user_data_128: tb_uint128_t,
+    pub user_data_64: u64,
+    pub user_data_32: u32,
+    pub timeout: u32,
+    pub ledger: u32,
+    pub code: u16,
+    pub flags: u16,
+    pub timestamp: u64,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, ::bytemuck::Pod, ::bytemuck::Zeroable)]
+pub struct tb_create_accounts_result_t {
+    pub index: u32,
+    pub result: u32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, ::bytemuck::Pod, ::bytemuck::Zeroable)]
+pub struct tb_create_transfers_result_t {
+    pub index: u32,
+    pub result: u32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, ::bytemuck::Pod, ::bytemuck::Zeroable)]
+pub struct tb_account_filter_t {
+    pub account_id: tb_uint128_t,
+    pub timestamp_min: u64,
+    pub timestamp_max: u64,
+    pub limit: u32,
+    pub flags: u32,
+    pub reserved: [u8; 24],
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, ::bytemuck::Pod, ::bytemuck::Zeroable)]
+pub struct tb_account_balance_t {
+    pub debits_pending: tb_uint128_t,
+    pub debits_posted: tb_uint128_t,
+    pub credits_pending: tb_uint128_t,
+    pub credits_posted: tb_uint128_t,
+    pub timestamp: u64,
+    pub reserved: [u8; 56],
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct tb_packet_t {
+    pub next: *mut tb_packet_t,
+    pub user_data: *mut ::std::os::raw::c_void,
+    pub operation: u8,
+    pub status: u8,
+    pub data_size: u32,
+    pub data: *mut ::std::os::raw::c_void,
+}
+pub type tb_client_t = *mut ::std::os::raw::c_void;
+extern "C" {
+    pub fn tb_client_init(
+        out_client: *mut tb_client_t,
+        cluster_id: tb_uint128_t,
+        address_ptr: *const ::std::os::raw::c_char,
+        address_len: u32,
+        packets_count: u32,
+        on_completion_ctx: usize,
+        on_completion_fn: ::std::option::Option<
+            unsafe extern "C" fn(
+                arg1: usize,
+                arg2: tb_client_t,
+                arg3: *mut tb_packet_t,
+                arg4: *const u8,
+                arg5: u32,
+            ),
+        >,
+    ) -> TB_STATUS::Type;
+    pub fn tb_client_init_echo(
+        out_client: *mut tb_client_t,
+        cluster_id: tb_uint128_t,
+        address_ptr: *const ::std::os::raw::c_char,
+        address_len: u32,
+        packets_count: u32,
+        on_completion_ctx: usize,
+        on_completion_fn: ::std::option::Option<
+            unsafe extern "C" fn(
+                arg1: usize,
+                arg2: tb_client_t,
+                arg3: *mut tb_packet_t,
+                arg4: *const u8,
+                arg5: u32,
+            ),
+        >,
+    ) -> TB_STATUS::Type;
+    pub fn tb_client_acquire_packet(
+        client: tb_client_t,
+        out_packet: *mut *mut tb_packet_t,
+    ) -> TB_PACKET_ACQUIRE_STATUS::Type;
+    pub fn tb_client_release_packet(client: tb_client_t, packet: *mut tb_packet_t);
+    pub fn tb_client_submit(client: tb_client_t, packet: *mut tb_packet_t);
+    pub fn tb_client_deinit(client: tb_client_t);
+}